@@ -4,6 +4,7 @@ var filesToCache = [
   './index.html',
   './adarsh_website.js',
   './adarsh_website.wasm',
+  './quotes.json',
 ];
 
 /* Start the service worker and cache all of the app's content */
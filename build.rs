@@ -0,0 +1,145 @@
+//! Generates responsive sizes of the project images in `assets/projects` and a
+//! manifest of them (`$OUT_DIR/project_images.rs`, included by `src/images.rs`).
+//!
+//! The generated files go to `$OUT_DIR/project-images`. Native builds embed
+//! them; for the web build, `cargo run -- export` writes them to
+//! `dist/projects`.
+//!
+//! Also embeds the optional Devanagari font (`$OUT_DIR/fonts.rs`, included by `src/i18n.rs`).
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageFormat};
+
+#[path = "build/webp.rs"]
+mod webp;
+
+/// Widths (in pixels) of the smaller copies, in addition to the original size.
+const WIDTHS: [u32; 3] = [320, 640, 1280];
+const JPEG_QUALITY: u8 = 85;
+
+fn main() {
+    embed_fonts();
+
+    let manifest_dir = PathBuf::from(env("CARGO_MANIFEST_DIR"));
+    let source_dir = manifest_dir.join("assets/projects");
+    let out_dir = PathBuf::from(env("OUT_DIR")).join("project-images");
+    // The directory itself for added and removed images, its files for edited ones.
+    println!("cargo:rerun-if-changed={}", source_dir.display());
+
+    // Start afresh so that only the images in the manifest are left to export.
+    if out_dir.exists() {
+        std::fs::remove_dir_all(&out_dir).expect("failed to clear the output directory");
+    }
+    std::fs::create_dir_all(&out_dir).expect("failed to create the output directory");
+
+    let mut sources: Vec<PathBuf> = std::fs::read_dir(&source_dir)
+        .expect("failed to read assets/projects")
+        .map(|entry| entry.expect("failed to read assets/projects").path())
+        .filter(|path| ImageFormat::from_path(path).is_ok())
+        .collect();
+    sources.sort();
+
+    let mut manifest = String::from("// Generated by build.rs from assets/projects.\n\n");
+    manifest.push_str("pub static PROJECT_IMAGES: &[ImageAsset] = &[\n");
+    for source in &sources {
+        println!("cargo:rerun-if-changed={}", source.display());
+        let image = image::open(source)
+            .unwrap_or_else(|err| panic!("failed to decode {}: {err}", source.display()));
+        let (width, height) = image.dimensions();
+        let file_name = source.file_name().unwrap().to_string_lossy();
+        writeln!(
+            manifest,
+            "    ImageAsset {{\n        path: {:?},\n        size: [{width}, {height}],\n        variants: &[",
+            format!("projects/{file_name}"),
+        )
+        .unwrap();
+
+        let original = encode_variant(source, &image, width);
+        let original_len = original.1.len();
+        let smaller = WIDTHS
+            .into_iter()
+            .filter(|&w| w < width)
+            .map(|w| (w, encode_variant(source, &image, w)))
+            // Resampling can add so many colours that a lossless copy grows; keep only copies that save bytes.
+            .filter(|(_, (_, bytes))| bytes.len() < original_len);
+        for (variant_width, (file, bytes)) in smaller.chain([(width, original)]) {
+            let path = out_dir.join(&file);
+            std::fs::write(&path, &bytes).expect("failed to write a project image");
+            writeln!(
+                manifest,
+                "            ImageVariant {{\n                width: {variant_width},\n                file: {file:?},\n                #[cfg(not(target_arch = \"wasm32\"))]\n                bytes: include_bytes!({:?}),\n            }},",
+                path.display().to_string(),
+            )
+            .unwrap();
+        }
+        manifest.push_str("        ],\n    },\n");
+    }
+    manifest.push_str("];\n");
+    std::fs::write(out_dir.with_file_name("project_images.rs"), manifest)
+        .expect("failed to write the image manifest");
+}
+
+/// egui ships no font with Devanagari glyphs, which the Hindi translation needs.
+/// The font is not in the repository; builds without it do not offer Hindi.
+fn embed_fonts() {
+    let fonts_dir = PathBuf::from(env("CARGO_MANIFEST_DIR")).join("assets/fonts");
+    println!("cargo:rerun-if-changed={}", fonts_dir.display());
+    let font = fonts_dir.join("NotoSansDevanagari-Regular.ttf");
+    let devanagari = if font.exists() {
+        format!("Some(include_bytes!({:?}))", font.display().to_string())
+    } else {
+        "None".to_owned()
+    };
+    let fonts = format!(
+        "// Generated by build.rs from assets/fonts.\n\npub const DEVANAGARI_FONT: Option<&[u8]> = {devanagari};\n"
+    );
+    let out_dir = PathBuf::from(env("OUT_DIR"));
+    std::fs::write(out_dir.join("fonts.rs"), fonts).expect("failed to write fonts.rs");
+}
+
+/// Resizes the image to the given width, returning the file name and contents.
+///
+/// Photos stay JPEG, as lossless WebP would be larger and lossy WebP needs
+/// libwebp. Everything else becomes lossless WebP, which is smaller than PNG
+/// for screenshots. The original size of a JPEG or WebP is copied as is.
+fn encode_variant(source: &Path, image: &DynamicImage, width: u32) -> (String, Vec<u8>) {
+    let stem = source.file_stem().unwrap().to_string_lossy();
+    let format = ImageFormat::from_path(source).unwrap();
+    let extension = if format == ImageFormat::Jpeg {
+        "jpg"
+    } else {
+        "webp"
+    };
+    let file = format!("{stem}-{width}.{extension}");
+
+    if width == image.width() && matches!(format, ImageFormat::Jpeg | ImageFormat::WebP) {
+        return (
+            file,
+            std::fs::read(source).expect("failed to read a project image"),
+        );
+    }
+
+    let height = (u64::from(image.height()) * u64::from(width) / u64::from(image.width())).max(1);
+    let resized = if width == image.width() {
+        image.clone()
+    } else {
+        image.resize_exact(width, height as u32, FilterType::Lanczos3)
+    };
+    if format == ImageFormat::Jpeg {
+        let mut bytes = Vec::new();
+        JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+            .encode_image(&resized.to_rgb8())
+            .expect("failed to encode a JPEG");
+        (file, bytes)
+    } else {
+        (file, webp::encode_lossless(&resized.to_rgba8()))
+    }
+}
+
+fn env(name: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| panic!("{name} is not set"))
+}
@@ -0,0 +1,598 @@
+//! A lossless WebP (VP8L) encoder for the project images.
+//!
+//! The `image` crate only encodes WebP through libwebp, a C library. This
+//! covers the part of the format that pays off for screenshots: the
+//! subtract-green, predictor and colour-indexing transforms, and copies of
+//! earlier pixels. It uses no colour cache and one set of prefix codes for
+//! the whole image. See <https://developers.google.com/speed/webp/docs/webp_lossless_bitstream_specification>.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap};
+
+use image::RgbaImage;
+
+/// Predictor tiles are `1 << PREDICTOR_BITS` pixels square.
+const PREDICTOR_BITS: u32 = 4;
+/// The predictor modes tried for each tile; the others use the top-right pixel
+/// and rarely win.
+const PREDICTOR_MODES: [u8; 9] = [1, 2, 4, 6, 7, 8, 11, 12, 13];
+/// Copies shorter than this cost more than the literals they replace.
+const MIN_COPY: usize = 3;
+const MAX_COPY: usize = 4096;
+const MAX_DISTANCE: usize = (1 << 20) - 120;
+/// Earlier positions are found in a hash table of this many bits, trying at
+/// most `MAX_CHAIN` with the same hash.
+const HASH_BITS: u32 = 16;
+const MAX_CHAIN: usize = 32;
+/// Distance codes of the pixel above and the pixel to the left.
+const ABOVE: u32 = 1;
+const LEFT: u32 = 2;
+const LENGTH_PREFIXES: usize = 24;
+const DISTANCE_PREFIXES: usize = 40;
+/// The order code lengths of the code length code are written in.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    17, 18, 0, 1, 2, 3, 4, 5, 16, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+];
+
+/// Encodes the image as a lossless WebP file.
+pub fn encode_lossless(image: &RgbaImage) -> Vec<u8> {
+    let (width, height) = image.dimensions();
+    assert!(
+        (1..=1 << 14).contains(&width) && (1..=1 << 14).contains(&height),
+        "WebP images are at most 16384 pixels wide and high"
+    );
+    let width = width as usize;
+    let pixels: Vec<u32> = image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            u32::from_be_bytes([a, r, g, b])
+        })
+        .collect();
+    let alpha_used = pixels.iter().any(|&pixel| pixel >> 24 != 0xff);
+
+    let header = |bits: &mut BitWriter| {
+        bits.write(0x2f, 8);
+        bits.write(width as u32 - 1, 14);
+        bits.write(height - 1, 14);
+        bits.write(u32::from(alpha_used), 1);
+        bits.write(0, 3);
+    };
+    let mut predicted = BitWriter::default();
+    header(&mut predicted);
+    write_predicted(&mut predicted, pixels.clone(), width);
+    let mut data = predicted.finish();
+    // Few colours, as in most screenshots, are smaller as palette indices.
+    if let Some(palette) = palette(&pixels) {
+        let mut indexed = BitWriter::default();
+        header(&mut indexed);
+        write_indexed(&mut indexed, &pixels, width, &palette);
+        let indexed = indexed.finish();
+        if indexed.len() < data.len() {
+            data = indexed;
+        }
+    }
+
+    let padding = data.len() % 2;
+    let mut file = Vec::with_capacity(20 + data.len() + padding);
+    file.extend_from_slice(b"RIFF");
+    file.extend_from_slice(&(12 + data.len() as u32 + padding as u32).to_le_bytes());
+    file.extend_from_slice(b"WEBPVP8L");
+    file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    file.extend_from_slice(&data);
+    file.resize(file.len() + padding, 0);
+    file
+}
+
+/// Writes the image with the subtract-green and predictor transforms.
+fn write_predicted(bits: &mut BitWriter, mut pixels: Vec<u32>, width: usize) {
+    // The decoder undoes the transforms in the opposite order.
+    bits.write(1, 1);
+    bits.write(2, 2);
+    subtract_green(&mut pixels);
+
+    bits.write(1, 1);
+    bits.write(0, 2);
+    bits.write(PREDICTOR_BITS - 2, 3);
+    let (modes, tiles_wide) = choose_predictors(&pixels, width);
+    let residuals = predict(&pixels, width, &modes, tiles_wide);
+    let mode_pixels: Vec<u32> = modes
+        .iter()
+        .map(|&mode| 0xff00_0000 | u32::from(mode) << 8)
+        .collect();
+    write_image(bits, &mode_pixels, tiles_wide, false);
+    bits.write(0, 1);
+
+    write_image(bits, &residuals, width, true);
+}
+
+/// Writes the image as indices into the palette, packing several into a
+/// pixel when there are at most 16 colours.
+fn write_indexed(bits: &mut BitWriter, pixels: &[u32], width: usize, palette: &[u32]) {
+    bits.write(1, 1);
+    bits.write(3, 2);
+    bits.write(palette.len() as u32 - 1, 8);
+    // Each colour is stored as the difference from the previous one.
+    let deltas: Vec<u32> = std::iter::once(palette[0])
+        .chain(palette.windows(2).map(|pair| sub_pixels(pair[1], pair[0])))
+        .collect();
+    write_image(bits, &deltas, palette.len(), false);
+    bits.write(0, 1);
+
+    let index_bits = match palette.len() {
+        0..=2 => 1,
+        3..=4 => 2,
+        5..=16 => 4,
+        _ => 8,
+    };
+    let per_pixel = 8 / index_bits;
+    let packed_width = (width + per_pixel - 1) / per_pixel;
+    let packed: Vec<u32> = pixels
+        .chunks(width)
+        .flat_map(|row| {
+            row.chunks(per_pixel).map(|chunk| {
+                let indices = chunk.iter().enumerate().fold(0, |packed, (i, pixel)| {
+                    let index = palette.binary_search(pixel).unwrap() as u32;
+                    packed | index << (i * index_bits)
+                });
+                0xff00_0000 | indices << 8
+            })
+        })
+        .collect();
+    write_image(bits, &packed, packed_width, true);
+}
+
+/// The colours of the image, sorted, if there are at most 256.
+fn palette(pixels: &[u32]) -> Option<Vec<u32>> {
+    let mut colours = BTreeSet::new();
+    for &pixel in pixels {
+        colours.insert(pixel);
+        if colours.len() > 256 {
+            return None;
+        }
+    }
+    Some(colours.into_iter().collect())
+}
+
+/// Bits written least significant first, as VP8L reads them.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: u32) {
+        self.buffer |= u64::from(value) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Writes a prefix code, whose bits are read most significant first.
+    fn write_code(&mut self, code: Code) {
+        if code.length > 0 {
+            let length = u32::from(code.length);
+            self.write(code.bits.reverse_bits() >> (32 - length), length);
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn subtract_green(pixels: &mut [u32]) {
+    for pixel in pixels {
+        let green = (*pixel >> 8) & 0xff;
+        let red = ((*pixel >> 16).wrapping_sub(green)) & 0xff;
+        let blue = (*pixel).wrapping_sub(green) & 0xff;
+        *pixel = (*pixel & 0xff00_ff00) | red << 16 | blue;
+    }
+}
+
+/// The predictor mode for each tile that leaves the smallest residuals, and
+/// the number of tiles in a row.
+fn choose_predictors(pixels: &[u32], width: usize) -> (Vec<u8>, usize) {
+    let height = pixels.len() / width;
+    let tile = 1 << PREDICTOR_BITS;
+    let tiles_wide = (width + tile - 1) / tile;
+    let tiles_high = (height + tile - 1) / tile;
+    let mut modes = Vec::with_capacity(tiles_wide * tiles_high);
+    for tile_y in 0..tiles_high {
+        for tile_x in 0..tiles_wide {
+            let cost = |mode: u8| -> u32 {
+                let mut cost = 0;
+                for y in (tile_y * tile).max(1)..((tile_y + 1) * tile).min(height) {
+                    for x in (tile_x * tile).max(1)..((tile_x + 1) * tile).min(width) {
+                        let index = y * width + x;
+                        let residual =
+                            sub_pixels(pixels[index], prediction(pixels, width, index, mode));
+                        cost += residual
+                            .to_le_bytes()
+                            .iter()
+                            .map(|&byte| u32::from(byte.min(byte.wrapping_neg())))
+                            .sum::<u32>();
+                    }
+                }
+                cost
+            };
+            let best = PREDICTOR_MODES
+                .into_iter()
+                .min_by_key(|&mode| cost(mode))
+                .unwrap();
+            modes.push(best);
+        }
+    }
+    (modes, tiles_wide)
+}
+
+/// The difference between each pixel and its prediction.
+fn predict(pixels: &[u32], width: usize, modes: &[u8], tiles_wide: usize) -> Vec<u32> {
+    (0..pixels.len())
+        .map(|index| {
+            let (x, y) = (index % width, index / width);
+            let predicted = match (x, y) {
+                (0, 0) => 0xff00_0000,
+                (_, 0) => pixels[index - 1],
+                (0, _) => pixels[index - width],
+                _ => {
+                    let tile = (y >> PREDICTOR_BITS) * tiles_wide + (x >> PREDICTOR_BITS);
+                    prediction(pixels, width, index, modes[tile])
+                }
+            };
+            sub_pixels(pixels[index], predicted)
+        })
+        .collect()
+}
+
+/// The prediction of a pixel that is not in the top row or left column.
+fn prediction(pixels: &[u32], width: usize, index: usize, mode: u8) -> u32 {
+    let left = pixels[index - 1];
+    let top = pixels[index - width];
+    let top_left = pixels[index - width - 1];
+    match mode {
+        1 => left,
+        2 => top,
+        4 => top_left,
+        6 => average(left, top_left),
+        7 => average(left, top),
+        8 => average(top_left, top),
+        11 => select(left, top, top_left),
+        12 => per_channel(left, top, top_left, |l, t, tl| l + t - tl),
+        13 => per_channel(average(left, top), top_left, 0, |a, b, _| a + (a - b) / 2),
+        _ => unreachable!("predictor mode {mode} is not tried"),
+    }
+}
+
+fn average(a: u32, b: u32) -> u32 {
+    per_channel(a, b, 0, |a, b, _| (a + b) / 2)
+}
+
+/// Whichever of the left and top pixels is closer to `left + top - top_left`.
+fn select(left: u32, top: u32, top_left: u32) -> u32 {
+    let distance = |a: u32, b: u32| -> i32 {
+        (0..4)
+            .map(|shift| {
+                let channel = |pixel: u32| ((pixel >> (8 * shift)) & 0xff) as i32;
+                (channel(a) - channel(b)).abs()
+            })
+            .sum()
+    };
+    // The estimate is as far from the left pixel as the top one is from the
+    // top-left one, and the other way round.
+    if distance(top, top_left) < distance(left, top_left) {
+        left
+    } else {
+        top
+    }
+}
+
+/// Applies `f` to each channel of the pixels, clamping the results to a byte.
+fn per_channel(a: u32, b: u32, c: u32, f: impl Fn(i32, i32, i32) -> i32) -> u32 {
+    (0..4).fold(0, |pixel, shift| {
+        let channel = |pixel: u32| ((pixel >> (8 * shift)) & 0xff) as i32;
+        pixel | (f(channel(a), channel(b), channel(c)).clamp(0, 255) as u32) << (8 * shift)
+    })
+}
+
+/// Subtracts each channel modulo 256.
+fn sub_pixels(a: u32, b: u32) -> u32 {
+    let a = a.to_le_bytes();
+    let b = b.to_le_bytes();
+    u32::from_le_bytes(std::array::from_fn(|i| a[i].wrapping_sub(b[i])))
+}
+
+enum Symbol {
+    Literal(u32),
+    Copy { length: usize, distance_code: u32 },
+}
+
+/// Writes an entropy-coded image: the prefix codes, then the pixels.
+fn write_image(bits: &mut BitWriter, pixels: &[u32], width: usize, main_image: bool) {
+    // No colour cache.
+    bits.write(0, 1);
+    if main_image {
+        // One set of prefix codes for the whole image.
+        bits.write(0, 1);
+    }
+
+    let symbols = backward_references(pixels, width);
+    let mut green = vec![0; 256 + LENGTH_PREFIXES];
+    let mut red = vec![0; 256];
+    let mut blue = vec![0; 256];
+    let mut alpha = vec![0; 256];
+    let mut distance = vec![0; DISTANCE_PREFIXES];
+    for symbol in &symbols {
+        match *symbol {
+            Symbol::Literal(pixel) => {
+                let [b, g, r, a] = pixel.to_le_bytes();
+                green[usize::from(g)] += 1;
+                red[usize::from(r)] += 1;
+                blue[usize::from(b)] += 1;
+                alpha[usize::from(a)] += 1;
+            }
+            Symbol::Copy {
+                length,
+                distance_code,
+            } => {
+                green[256 + prefix(length as u32).0 as usize] += 1;
+                distance[prefix(distance_code).0 as usize] += 1;
+            }
+        }
+    }
+    let [green, red, blue, alpha, distance] =
+        [green, red, blue, alpha, distance].map(|histogram| write_prefix_code(bits, &histogram));
+
+    for symbol in symbols {
+        match symbol {
+            Symbol::Literal(pixel) => {
+                let [b, g, r, a] = pixel.to_le_bytes();
+                bits.write_code(green[usize::from(g)]);
+                bits.write_code(red[usize::from(r)]);
+                bits.write_code(blue[usize::from(b)]);
+                bits.write_code(alpha[usize::from(a)]);
+            }
+            Symbol::Copy {
+                length,
+                distance_code,
+            } => {
+                let (length_prefix, extra_bits, extra) = prefix(length as u32);
+                bits.write_code(green[256 + length_prefix as usize]);
+                bits.write(extra, extra_bits);
+                let (distance_prefix, extra_bits, extra) = prefix(distance_code);
+                bits.write_code(distance[distance_prefix as usize]);
+                bits.write(extra, extra_bits);
+            }
+        }
+    }
+}
+
+/// Replaces pixels repeating earlier ones with copies, found by hashing pairs
+/// of pixels. Runs repeating the pixel to the left or above are tried first,
+/// as their distances have the shortest codes.
+fn backward_references(pixels: &[u32], width: usize) -> Vec<Symbol> {
+    let match_length = |index: usize, earlier: usize| {
+        pixels[index..]
+            .iter()
+            .zip(&pixels[earlier..])
+            .take(MAX_COPY)
+            .take_while(|(pixel, earlier)| pixel == earlier)
+            .count()
+    };
+    let hash = |index: usize| {
+        let pair = u64::from(pixels[index]) << 32 | u64::from(pixels[index + 1]);
+        (pair.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (64 - HASH_BITS)) as usize
+    };
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut previous = vec![usize::MAX; pixels.len()];
+
+    let mut symbols = Vec::new();
+    let mut index = 0;
+    while index < pixels.len() {
+        let mut best = (0, 0);
+        for distance in [1, width] {
+            if distance <= index {
+                let length = match_length(index, index - distance);
+                if length > best.0 {
+                    best = (length, distance);
+                }
+            }
+        }
+        if index + 1 < pixels.len() {
+            let mut candidate = head[hash(index)];
+            for _ in 0..MAX_CHAIN {
+                if candidate == usize::MAX || index - candidate > MAX_DISTANCE {
+                    break;
+                }
+                let length = match_length(index, candidate);
+                if length > best.0 {
+                    best = (length, index - candidate);
+                }
+                candidate = previous[candidate];
+            }
+        }
+
+        let (length, distance) = best;
+        let length = if length >= MIN_COPY { length } else { 1 };
+        if length > 1 {
+            symbols.push(Symbol::Copy {
+                length,
+                distance_code: distance_code(distance, width),
+            });
+        } else {
+            symbols.push(Symbol::Literal(pixels[index]));
+        }
+        let hashed = (index + length).min(pixels.len() - 1);
+        for (copied, previous) in previous.iter_mut().enumerate().take(hashed).skip(index) {
+            let hash = hash(copied);
+            *previous = head[hash];
+            head[hash] = copied;
+        }
+        index += length;
+    }
+    symbols
+}
+
+/// The code of a copy distance: the 120 smallest codes stand for nearby
+/// pixels, of which only the ones to the left and above are used here.
+fn distance_code(distance: usize, width: usize) -> u32 {
+    if distance == width {
+        ABOVE
+    } else if distance == 1 {
+        LEFT
+    } else {
+        distance as u32 + 120
+    }
+}
+
+/// The prefix, number of extra bits and extra bits a length or distance code
+/// (at least 1) is written as.
+fn prefix(value: u32) -> (u32, u32, u32) {
+    let value = value - 1;
+    if value < 4 {
+        return (value, 0, 0);
+    }
+    let highest_bit = 31 - value.leading_zeros();
+    let second_highest_bit = (value >> (highest_bit - 1)) & 1;
+    let extra_bits = highest_bit - 1;
+    (
+        2 * highest_bit + second_highest_bit,
+        extra_bits,
+        value & ((1 << extra_bits) - 1),
+    )
+}
+
+#[derive(Clone, Copy, Default)]
+struct Code {
+    bits: u32,
+    length: u8,
+}
+
+/// Writes a prefix code for symbols with the given counts, returning the code
+/// of each symbol.
+fn write_prefix_code(bits: &mut BitWriter, histogram: &[u32]) -> Vec<Code> {
+    let used: Vec<usize> = (0..histogram.len())
+        .filter(|&symbol| histogram[symbol] > 0)
+        .collect();
+    if used.len() <= 1 {
+        // A simple code with one symbol, which takes no bits to write.
+        let symbol = used.first().copied().unwrap_or(0) as u32;
+        assert!(symbol < 256, "a lone symbol must fit in 8 bits");
+        bits.write(1, 1);
+        bits.write(0, 1);
+        if symbol < 2 {
+            bits.write(0, 1);
+            bits.write(symbol, 1);
+        } else {
+            bits.write(1, 1);
+            bits.write(symbol, 8);
+        }
+        return vec![Code::default(); histogram.len()];
+    }
+
+    let lengths = code_lengths(histogram, 15);
+    let mut length_histogram = [0; 19];
+    for &length in &lengths {
+        length_histogram[usize::from(length)] += 1;
+    }
+    let length_lengths = code_lengths(&length_histogram, 7);
+    let length_codes = canonical_codes(&length_lengths);
+    let written = CODE_LENGTH_ORDER
+        .iter()
+        .rposition(|&length| length_lengths[length] > 0)
+        .map_or(0, |position| position + 1)
+        .max(4);
+    bits.write(0, 1);
+    bits.write(written as u32 - 4, 4);
+    for &length in &CODE_LENGTH_ORDER[..written] {
+        bits.write(u32::from(length_lengths[length]), 3);
+    }
+    // Code lengths follow for every symbol of the alphabet.
+    bits.write(0, 1);
+    for &length in &lengths {
+        bits.write_code(length_codes[usize::from(length)]);
+    }
+    canonical_codes(&lengths)
+}
+
+/// Huffman code lengths of at most `max_length` bits for the given counts.
+///
+/// When the optimal code is too deep, the counts are flattened until it fits.
+fn code_lengths(histogram: &[u32], max_length: u8) -> Vec<u8> {
+    let mut counts = histogram.to_vec();
+    loop {
+        let leaves: Vec<usize> = (0..counts.len()).filter(|&i| counts[i] > 0).collect();
+        let mut lengths = vec![0; counts.len()];
+        if leaves.len() == 1 {
+            lengths[leaves[0]] = 1;
+            return lengths;
+        }
+        let mut parents = vec![usize::MAX; 2 * leaves.len()];
+        let mut heap: BinaryHeap<Reverse<(u64, usize)>> = leaves
+            .iter()
+            .enumerate()
+            .map(|(node, &symbol)| Reverse((u64::from(counts[symbol]), node)))
+            .collect();
+        let mut next = leaves.len();
+        while let (Some(Reverse((a, a_node))), Some(Reverse((b, b_node)))) =
+            (heap.pop(), heap.pop())
+        {
+            parents[a_node] = next;
+            parents[b_node] = next;
+            heap.push(Reverse((a + b, next)));
+            next += 1;
+        }
+        for (node, &symbol) in leaves.iter().enumerate() {
+            let mut depth = 0;
+            let mut ancestor = parents[node];
+            while ancestor != usize::MAX {
+                depth += 1;
+                ancestor = parents[ancestor];
+            }
+            lengths[symbol] = depth;
+        }
+        if lengths.iter().all(|&length| length <= max_length) {
+            return lengths;
+        }
+        for count in counts.iter_mut().filter(|count| **count > 0) {
+            *count = (*count >> 1).max(1);
+        }
+    }
+}
+
+/// The canonical code of each symbol, as in DEFLATE. A code with a single
+/// symbol takes no bits.
+fn canonical_codes(lengths: &[u8]) -> Vec<Code> {
+    if lengths.iter().filter(|&&length| length > 0).count() <= 1 {
+        return vec![Code::default(); lengths.len()];
+    }
+    let mut counts = [0; 16];
+    for &length in lengths.iter().filter(|&&length| length > 0) {
+        counts[usize::from(length)] += 1;
+    }
+    let mut code = 0;
+    let mut next_code = counts.map(|count| {
+        let first = code;
+        code = (code + count) << 1;
+        first
+    });
+    lengths
+        .iter()
+        .map(|&length| {
+            if length == 0 {
+                return Code::default();
+            }
+            let bits = next_code[usize::from(length)];
+            next_code[usize::from(length)] += 1;
+            Code { bits, length }
+        })
+        .collect()
+}
@@ -1,19 +1,37 @@
-enum TextType {
+use std::collections::BTreeSet;
+
+use crate::apps::Registry;
+use crate::contact::{ContactCard, ContactStyle};
+use crate::content::{Content, Paragraph, Project, Section, SectionBody, Status, CONTENT};
+use crate::desktop::Desktop;
+use crate::gallery::Gallery;
+use crate::i18n::{self, tr, tr_with, Locale};
+use crate::images;
+use crate::markdown;
+use crate::quotes::{self, QUOTES};
+use crate::reader;
+use crate::search::{self, SearchIndex, Target};
+use crate::skills;
+use crate::timeline::Timeline;
+use crate::toc::SectionRegistry;
+
+#[derive(Clone, Copy)]
+pub(crate) enum TextType {
     Heading,
     SubHeading,
     Paragraph,
     Project,
 }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq)]
-enum Window {
-    About,
-    Projects,
-    GraphingCalculator,
-    NoteMaker,
-    ExpenseCalculator,
-    Resume,
-    // Http,
+/// How the central panel presents the portfolio.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, Clone, Copy)]
+enum Layout {
+    /// All sections one below the other.
+    Linear,
+    /// Education, experience and projects on a time axis.
+    Timeline,
+    /// Windows opened from a taskbar, like a desktop.
+    Desktop,
 }
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -23,6 +41,57 @@ pub struct Website {
     // Example stuff:
     label: String,
     main_menu_size: f32,
+    layout: Layout,
+    /// The windows open in the desktop layout.
+    desktop: Desktop,
+    /// Shows the central panel as a single column of high-contrast text without images.
+    reader_mode: bool,
+    /// The language chosen in the top panel; `None` follows the browser's language.
+    locale: Option<Locale>,
+    /// Skills selected in the "Strengths" section. When non-empty, only
+    /// projects using at least one of them are listed.
+    skill_filter: BTreeSet<String>,
+    /// Whether the "Strengths" section shows charts below the skills.
+    show_skill_charts: bool,
+    /// Texts of the quotes marked as favorites; texts stay valid when quotes are added.
+    favorite_quotes: BTreeSet<String>,
+    /// Index of the quote in the top panel; `None` shows the quote of the day.
+    #[serde(skip)]
+    quote: Option<usize>,
+    /// Id of the course whose detail window is open.
+    #[serde(skip)]
+    open_course: Option<String>,
+    /// Id of the project whose detail window is open.
+    #[serde(skip)]
+    open_project: Option<String>,
+    #[serde(skip)]
+    gallery: Gallery,
+    /// Anchor (section or project id) to scroll to once it is laid out.
+    #[serde(skip)]
+    scroll_target: Option<String>,
+    /// The anchor in the URL fragment on the web, as last read from or written to it.
+    #[serde(skip)]
+    url_anchor: Option<String>,
+    #[serde(skip)]
+    contact_card: ContactCard,
+    /// The tools of the desktop layout; each saves its own state.
+    #[serde(skip)]
+    apps: Registry,
+    /// Headings of the central panel, for the table of contents.
+    #[serde(skip)]
+    toc: SectionRegistry,
+    #[serde(skip)]
+    timeline: Timeline,
+    #[serde(skip)]
+    search_index: SearchIndex,
+    #[serde(skip)]
+    search_query: String,
+    /// Words of `search_query`, highlighted wherever they appear.
+    #[serde(skip)]
+    search_words: Vec<String>,
+    /// Documents of `search_index` matching `search_query`, best match first.
+    #[serde(skip)]
+    search_hits: Vec<usize>,
     // #[serde(skip)]
     // http_app: HttpApp,
 }
@@ -32,6 +101,27 @@ impl Default for Website {
         Self {
             label: "Adarsh Das".to_owned(),
             main_menu_size: 17.0,
+            layout: Layout::Linear,
+            desktop: Desktop::default(),
+            reader_mode: false,
+            locale: None,
+            skill_filter: BTreeSet::new(),
+            show_skill_charts: false,
+            favorite_quotes: BTreeSet::new(),
+            quote: None,
+            open_course: None,
+            open_project: None,
+            gallery: Gallery::default(),
+            scroll_target: None,
+            url_anchor: None,
+            contact_card: ContactCard::default(),
+            apps: Registry::default(),
+            toc: SectionRegistry::default(),
+            timeline: Timeline::default(),
+            search_index: SearchIndex::default(),
+            search_query: String::new(),
+            search_words: Vec::new(),
+            search_hits: Vec::new(),
             // http_app: HttpApp::default(),
         }
     }
@@ -45,11 +135,560 @@ impl Website {
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
+        let mut website: Self = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            .unwrap_or_default();
+        // A locale saved by a build that could show it, but this one cannot, is forgotten.
+        website.locale = website.locale.filter(|locale| Locale::ALL.contains(locale));
+        i18n::set_locale(website.locale.unwrap_or_else(Locale::detect));
+        i18n::install_fonts(&cc.egui_ctx);
+        images::check_content(&CONTENT);
+        website.search_index = SearchIndex::new(&CONTENT);
+        website.timeline = Timeline::new(&CONTENT);
+        website.apps = Registry::new(cc.storage);
+        #[cfg(target_arch = "wasm32")]
+        crate::web::repaint_on_navigation(&cc.egui_ctx);
+        website
+    }
+
+    fn scroll_if_target(&mut self, response: &egui::Response, anchor: &str) {
+        if self.scroll_target.as_deref() == Some(anchor) {
+            response.scroll_to_me(Some(egui::Align::Min));
+            self.scroll_target = None;
+        }
+    }
+
+    /// Scrolls to a section or project, clearing the skill filter if it hides the project.
+    fn scroll_to(&mut self, content: &Content, anchor: &str) {
+        if let Some(project) = content.project(anchor) {
+            if !self.skill_filter.is_empty() && !project.uses_any(&self.skill_filter) {
+                self.skill_filter.clear();
+            }
+        }
+        self.layout = Layout::Linear;
+        self.scroll_target = Some(anchor.to_owned());
+    }
+
+    /// Like [`Self::scroll_to`], and on the web also records the anchor in the
+    /// URL fragment and browser history.
+    fn navigate(&mut self, content: &Content, anchor: &str) {
+        self.scroll_to(content, anchor);
+        #[cfg(target_arch = "wasm32")]
+        {
+            crate::web::push_fragment(anchor);
+            self.url_anchor = Some(anchor.to_owned());
+        }
+    }
+
+    /// Scrolls to the URL fragment when it changes, e.g. on page load or with the back button.
+    #[cfg(target_arch = "wasm32")]
+    fn follow_url_fragment(&mut self, content: &Content) {
+        // Wait until the headings have been laid out once, so there is something to scroll to.
+        if self.toc.entries().is_empty() {
+            return;
+        }
+        let fragment = crate::web::fragment();
+        if fragment != self.url_anchor {
+            if let Some(anchor) = &fragment {
+                self.scroll_to(content, anchor);
+            }
+            self.url_anchor = fragment;
         }
+    }
+
+    /// Points the URL fragment at the heading in view, without adding history entries.
+    #[cfg(target_arch = "wasm32")]
+    fn update_url_fragment(&mut self) {
+        let Some(active) = self.toc.active() else {
+            return;
+        };
+        if self.url_anchor.as_ref() == Some(&active.anchor) {
+            return;
+        }
+        // Keep the URL clean until the user scrolls away from the top.
+        let at_top = self.toc.entries()[0].anchor == active.anchor;
+        if at_top && self.url_anchor.is_none() {
+            return;
+        }
+        crate::web::replace_fragment(&active.anchor);
+        self.url_anchor = Some(active.anchor.clone());
+    }
+
+    fn go_to(&mut self, content: &Content, target: Target) {
+        match target {
+            Target::Section(anchor)
+            | Target::Project(anchor)
+            | Target::Skill { section: anchor } => {
+                self.navigate(content, &anchor);
+            }
+            Target::Course { section, course } => {
+                self.navigate(content, &section);
+                self.open_course = Some(course);
+            }
+        }
+    }
+
+    /// Links to the sections of the page, with the projects of the section
+    /// currently in view nested below it.
+    fn table_of_contents_ui(&mut self, ui: &mut egui::Ui, content: &Content) {
+        let active = self.toc.active().map(|entry| entry.anchor.clone());
+        let active_section = self.toc.active_section().map(|entry| entry.anchor.clone());
+        let mut in_active_section = false;
+        let mut clicked = None;
+        for entry in self.toc.entries() {
+            if entry.depth == 0 {
+                in_active_section = active_section.as_ref() == Some(&entry.anchor);
+            } else if !in_active_section {
+                continue;
+            }
+
+            let is_active = active.as_ref() == Some(&entry.anchor);
+            let response = if entry.depth == 0 {
+                let text = add_text(TextType::Paragraph, &format!("- {}", entry.title));
+                ui.selectable_label(is_active, text)
+            } else {
+                ui.indent(&entry.anchor, |ui| {
+                    let text = add_text(TextType::Paragraph, &entry.title).small();
+                    ui.selectable_label(is_active, text)
+                })
+                .inner
+            };
+            if response.clicked() {
+                clicked = Some(entry.anchor.clone());
+            }
+        }
+        if let Some(anchor) = clicked {
+            self.navigate(content, &anchor);
+        }
+    }
 
-        Default::default()
+    fn locale_ui(&mut self, ui: &mut egui::Ui) {
+        let current = i18n::locale();
+        let mut selected = current;
+        egui::ComboBox::from_id_source("locale")
+            .selected_text(current.native_name())
+            .show_ui(ui, |ui| {
+                for &locale in Locale::ALL {
+                    ui.selectable_value(&mut selected, locale, locale.native_name());
+                }
+            })
+            .response
+            .on_hover_text(tr("locale.language"));
+        if selected != current {
+            self.locale = Some(selected);
+            i18n::set_locale(selected);
+        }
+    }
+
+    /// The current quote with its author, and controls to move on or keep it.
+    fn quote_ui(&mut self, ui: &mut egui::Ui) {
+        if QUOTES.is_empty() {
+            return;
+        }
+        let index = self
+            .quote
+            .unwrap_or_else(quotes::quote_of_the_day)
+            .min(QUOTES.len() - 1);
+        let quote = &QUOTES[index];
+        ui.vertical_centered(|ui| {
+            ui.heading(add_text(TextType::Heading, &quote.text).italics());
+            ui.horizontal_wrapped(|ui| {
+                ui.label(
+                    add_text(
+                        TextType::Paragraph,
+                        &format!("— {}, {}", quote.author, quote.source),
+                    )
+                    .italics(),
+                );
+                if ui
+                    .button(add_text(TextType::Paragraph, "⟳"))
+                    .on_hover_text(tr("quotes.next"))
+                    .clicked()
+                {
+                    self.quote = Some((index + 1) % QUOTES.len());
+                }
+                let favorite = self.favorite_quotes.contains(&quote.text);
+                let (star, hint) = if favorite {
+                    ("★", tr("quotes.unfavorite"))
+                } else {
+                    ("☆", tr("quotes.favorite"))
+                };
+                if ui
+                    .button(add_text(TextType::Paragraph, star))
+                    .on_hover_text(hint)
+                    .clicked()
+                {
+                    if favorite {
+                        self.favorite_quotes.remove(&quote.text);
+                    } else {
+                        self.favorite_quotes.insert(quote.text.clone());
+                    }
+                }
+                ui.menu_button(
+                    add_text(TextType::Paragraph, tr("quotes.favorites")),
+                    |ui| {
+                        self.favorite_quotes_ui(ui);
+                    },
+                );
+            });
+        });
+    }
+
+    /// The favorite quotes, in the order of `assets/quotes.json`; clicking one shows it.
+    fn favorite_quotes_ui(&mut self, ui: &mut egui::Ui) {
+        let favorites: Vec<usize> = (0..QUOTES.len())
+            .filter(|&index| self.favorite_quotes.contains(&QUOTES[index].text))
+            .collect();
+        if favorites.is_empty() {
+            ui.label(add_text(TextType::Paragraph, tr("quotes.no_favorites")).weak());
+            return;
+        }
+        ui.set_max_width(400.0);
+        for index in favorites {
+            let quote = &QUOTES[index];
+            if ui
+                .selectable_label(
+                    self.quote == Some(index),
+                    add_text(TextType::Paragraph, &quote.text),
+                )
+                .on_hover_text(&quote.author)
+                .clicked()
+            {
+                self.quote = Some(index);
+                ui.close_menu();
+            }
+        }
+    }
+
+    /// Search box and results at the top of the side panel.
+    fn search_ui(&mut self, ui: &mut egui::Ui, content: &Content) {
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.search_query)
+                .hint_text(tr("search.hint"))
+                .font(egui::FontId::proportional(text_size(TextType::Paragraph)))
+                .desired_width(f32::INFINITY),
+        );
+        if response.changed() {
+            self.search_words = search::terms(&self.search_query);
+            self.search_hits = self.search_index.search(&self.search_query);
+        }
+        if self.search_words.is_empty() {
+            return;
+        }
+
+        if self.search_hits.is_empty() {
+            ui.label(add_text(TextType::Paragraph, tr("search.no_results")).weak());
+        }
+        let mut clicked = None;
+        for &hit in self.search_hits.iter().take(8) {
+            let document = self.search_index.document(hit);
+            ui.horizontal_wrapped(|ui| {
+                ui.label(egui::RichText::new(tr(document.kind)).small().weak());
+                let title = add_highlighted_text(
+                    ui,
+                    TextType::Paragraph,
+                    &document.title,
+                    &self.search_words,
+                );
+                if ui
+                    .add(egui::Label::new(title).sense(egui::Sense::click()))
+                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                    .clicked()
+                {
+                    clicked = Some(document.target.clone());
+                }
+            });
+        }
+        if self.search_hits.len() > 8 {
+            ui.label(
+                add_text(
+                    TextType::Paragraph,
+                    &tr_with(
+                        "search.more",
+                        &[("count", &(self.search_hits.len() - 8).to_string())],
+                    ),
+                )
+                .weak(),
+            );
+        }
+        if let Some(target) = clicked {
+            self.go_to(content, target);
+        }
+        ui.separator();
+    }
+
+    /// Detail window of the course selected in the "Coursework" section.
+    fn course_window(&mut self, ctx: &egui::Context, content: &Content) {
+        let Some(course) = self.open_course.as_deref().and_then(|id| content.course(id)) else {
+            return;
+        };
+        let mut open = true;
+        let mut clicked_project = None;
+        egui::Window::new(add_text(TextType::SubHeading, &course.name))
+            .id(egui::Id::new("course_details"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .default_width(400.0)
+            .show(ctx, |ui| {
+                if let Some(details) = course.details() {
+                    ui.label(add_text(TextType::Paragraph, &details).weak());
+                }
+                if let Some(grade) = &course.grade {
+                    ui.label(add_text(
+                        TextType::Paragraph,
+                        &tr_with("course.grade", &[("grade", grade.as_str())]),
+                    ));
+                }
+                ui.add_space(10.0);
+                ui.label(add_text(TextType::Paragraph, &course.syllabus));
+
+                let projects: Vec<&Project> = course
+                    .projects
+                    .iter()
+                    .filter_map(|id| content.project(id))
+                    .collect();
+                if !projects.is_empty() {
+                    ui.add_space(10.0);
+                    ui.label(add_text(TextType::Paragraph, tr("course.related_projects")).strong());
+                    for project in projects {
+                        if ui
+                            .link(add_text(TextType::Paragraph, &project.name))
+                            .clicked()
+                        {
+                            clicked_project = Some(project);
+                        }
+                    }
+                }
+
+                if !course.reports.is_empty() {
+                    ui.add_space(10.0);
+                    ui.label(add_text(TextType::Paragraph, tr("course.reports")).strong());
+                    for report in &course.reports {
+                        add_custom_hyperlink(
+                            ui,
+                            add_text(TextType::Paragraph, &report.title),
+                            &content.document_url(&report.path),
+                        );
+                    }
+                }
+            });
+        if let Some(project) = clicked_project {
+            self.navigate(content, &project.id);
+        }
+        if !open {
+            self.open_course = None;
+        }
+    }
+
+    fn open_project(&mut self, id: &str) {
+        if self.open_project.as_deref() != Some(id) {
+            self.open_project = Some(id.to_owned());
+            self.gallery = Gallery::default();
+        }
+    }
+
+    fn project_window(&mut self, ctx: &egui::Context, content: &Content) {
+        let Some(project) = self.open_project.as_deref().and_then(|id| content.project(id)) else {
+            return;
+        };
+        let mut open = true;
+        egui::Window::new(add_text(TextType::SubHeading, &project.name))
+            .id(egui::Id::new("project_details"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(700.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    project_details_ui(
+                        ui,
+                        ("project_details", &project.id),
+                        project,
+                        &mut self.gallery,
+                        &self.search_words,
+                    );
+                });
+            });
+        if !open || ctx.input(|input| input.key_pressed(egui::Key::Escape)) {
+            self.open_project = None;
+        }
+    }
+
+    /// Renders everything below the heading of a section.
+    fn add_section_body(&mut self, ui: &mut egui::Ui, content: &Content, section: &Section) {
+        match &section.body {
+            SectionBody::Text {
+                paragraphs,
+                contact_links,
+            } => {
+                for paragraph in paragraphs {
+                    add_paragraph(ui, paragraph, &self.search_words);
+                }
+                if *contact_links {
+                    self.contact_card.ui(ui, content, ContactStyle::Compact);
+                }
+                ui.add_space(10.0);
+                ui.separator();
+            }
+            SectionBody::Skills => {
+                ui.horizontal_wrapped(|ui| {
+                    for skill in &content.skills {
+                        let skill = &skill.name;
+                        let selected = self.skill_filter.contains(skill);
+                        let label = format!("{skill} ({})", content.project_count(skill));
+                        let label = add_highlighted_text(
+                            ui,
+                            TextType::Paragraph,
+                            &label,
+                            &self.search_words,
+                        );
+                        if ui
+                            .selectable_label(selected, label)
+                            .on_hover_text(tr("skills.filter_hint"))
+                            .clicked()
+                        {
+                            if selected {
+                                self.skill_filter.remove(skill);
+                            } else {
+                                self.skill_filter.insert(skill.clone());
+                            }
+                        }
+                    }
+                    ui.separator();
+                    ui.toggle_value(
+                        &mut self.show_skill_charts,
+                        add_text(TextType::Paragraph, tr("skills.charts")),
+                    );
+                });
+                if self.show_skill_charts {
+                    ui.add_space(10.0);
+                    skills::skill_charts_ui(ui, content);
+                }
+                if !self.skill_filter.is_empty() {
+                    ui.horizontal_wrapped(|ui| {
+                        let shown = content
+                            .projects
+                            .iter()
+                            .filter(|project| project.uses_any(&self.skill_filter))
+                            .count();
+                        ui.label(add_text(
+                            TextType::Paragraph,
+                            &tr_with(
+                                "skills.showing",
+                                &[
+                                    ("shown", &shown.to_string()),
+                                    ("total", &content.projects.len().to_string()),
+                                ],
+                            ),
+                        ));
+                        if ui
+                            .button(add_text(TextType::Paragraph, tr("skills.clear_filters")))
+                            .clicked()
+                        {
+                            self.skill_filter.clear();
+                        }
+                    });
+                }
+                ui.add_space(10.0);
+                ui.separator();
+            }
+            SectionBody::Courses => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.set_max_width(ui.available_width());
+                    for course in &content.courses {
+                        let open = self.open_course.as_ref() == Some(&course.id);
+                        let label = add_highlighted_text(
+                            ui,
+                            TextType::Paragraph,
+                            &course.name,
+                            &self.search_words,
+                        );
+                        if ui.selectable_label(open, label).clicked() {
+                            self.open_course = if open { None } else { Some(course.id.clone()) };
+                        }
+                    }
+                });
+                ui.add_space(10.0);
+                ui.separator();
+            }
+            SectionBody::Education => {
+                for entry in &content.education {
+                    add_heading(ui, TextType::Project, &entry.degree, &self.search_words);
+                    let details = format!(
+                        "{} · {} · {}",
+                        entry.institution,
+                        entry.location,
+                        entry.duration()
+                    );
+                    ui.label(add_highlighted_text(
+                        ui,
+                        TextType::Paragraph,
+                        &details,
+                        &self.search_words,
+                    ));
+                    for grade in &entry.grades {
+                        ui.label(add_text(TextType::Paragraph, &format!("• {grade}")));
+                    }
+                    ui.add_space(10.0);
+                }
+                ui.separator();
+            }
+            SectionBody::Achievements => {
+                let mut clicked_project = None;
+                for achievement in &content.achievements {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(add_highlighted_text(
+                            ui,
+                            TextType::Paragraph,
+                            &format!("• {}", achievement.title),
+                            &self.search_words,
+                        ));
+                        let project = achievement.project.as_deref();
+                        if let Some(project) = project.and_then(|id| content.project(id)) {
+                            let name = add_text(TextType::Paragraph, &project.name);
+                            if ui
+                                .link(name)
+                                .on_hover_text(tr("project.show_details"))
+                                .clicked()
+                            {
+                                clicked_project = Some(project.id.as_str());
+                            }
+                        }
+                    });
+                }
+                if let Some(id) = clicked_project {
+                    self.open_project(id);
+                }
+                ui.add_space(10.0);
+                ui.separator();
+            }
+            SectionBody::Projects { categories } => {
+                let projects: Vec<&Project> = content
+                    .projects_in(categories)
+                    .into_iter()
+                    .filter(|project| {
+                        self.skill_filter.is_empty() || project.uses_any(&self.skill_filter)
+                    })
+                    .collect();
+                if projects.is_empty() {
+                    ui.label(add_text(TextType::Paragraph, tr("skills.no_projects")).weak());
+                    ui.add_space(10.0);
+                    ui.separator();
+                }
+                for project in projects {
+                    let (response, open_details) = add_project(ui, project, &self.search_words);
+                    if open_details {
+                        self.open_project(&project.id);
+                    }
+                    self.toc.register(&project.id, &project.name, 1, &response);
+                    self.scroll_if_target(&response, &project.id);
+                }
+            }
+            SectionBody::Contacts => {
+                self.contact_card.ui(ui, content, ContactStyle::Full);
+            }
+        }
     }
 }
 
@@ -57,6 +696,7 @@ impl eframe::App for Website {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, eframe::APP_KEY, self);
+        self.apps.save(storage);
     }
 
     /// Called each time the UI needs repainting, which may be many times per second.
@@ -70,289 +710,361 @@ impl eframe::App for Website {
             ui.set_min_width(277.4);
             ui.horizontal(|ui| {
                 egui::widgets::global_dark_light_mode_switch(ui);
-                ui.vertical_centered(|ui| {
-                    ui.heading(
-                        add_text(TextType::Heading, "Reden ist Silber, Schweigen ist Gold")
-                            .italics(),
-                    );
-                    ui.heading(
-                        add_text(
-                            TextType::Paragraph,
-                            "- Büchmann, Georg (1895). Geflügelte Worte",
-                        )
-                        .italics(),
-                    );
-                });
+                self.locale_ui(ui);
+                self.quote_ui(ui);
                 ui.add_space(10.0);
             });
         });
 
-        let mut about_me_button: Option<egui::Response> = None;
-        let mut education_button: Option<egui::Response> = None;
-        let mut strengths_button: Option<egui::Response> = None;
-        let mut coursework_button: Option<egui::Response> = None;
-        let mut research_projects_button: Option<egui::Response> = None;
-        let mut work_experience_button: Option<egui::Response> = None;
-        let mut personal_projects_button: Option<egui::Response> = None;
+        let content = &*CONTENT;
+        #[cfg(target_arch = "wasm32")]
+        self.follow_url_fragment(content);
 
-        if window_width > 972.0 {
+        // Reader mode shows the side panel's sections in its single column.
+        if window_width > 972.0 && !self.reader_mode {
             egui::SidePanel::left("table_of_contents").show(ctx, |ui| {
                 // ui.heading(format!("{:?}", ui.available_size()));
-                ui.heading(add_text(TextType::SubHeading, "Table of Contents"));
-                about_me_button = Some( ui.link(add_text(TextType::Paragraph, "- About Me")));
-                education_button = Some(ui.link(add_text(TextType::Paragraph, "- Education")));
-                strengths_button = Some(ui.link(add_text(TextType::Paragraph, "- Strengths")));
-                coursework_button = Some(ui.link(add_text(TextType::Paragraph, "- Coursework")));
-                research_projects_button = Some(ui.link(add_text(TextType::Paragraph, "- Research Projects")));
-                work_experience_button = Some(ui.link(add_text(TextType::Paragraph, "- Work Experience, Competetions and Club activities")));
-                personal_projects_button = Some(ui.link(add_text(TextType::Paragraph, "- Hobby Projects")));
+                self.search_ui(ui, content);
+                ui.heading(add_text(TextType::SubHeading, tr("toc.title")));
+                self.table_of_contents_ui(ui, content);
                 ui.separator();
 
                 egui::ScrollArea::vertical().show(ui, |ui| {
-                    //
-                    // Contact Me
-                    //
                     ui.vertical(|ui| {
                         ui.set_max_width(ui.available_width());
 
-                        ui.heading(add_text(TextType::SubHeading, "Contact Me"));
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Email: adarshdas950@gmail.com"), "mailto:adarshdas950@gmail.com"); ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Phone Number: +91 85278 59660"), "tel:+918527859660"); ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Github"), "https://github.com/Saphereye"); ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Linkedin"), "https://www.linkedin.com/in/adarsh-das-8684ab240/"); ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Résumé"), "https://github.com/Saphereye/saphereye.github.io/blob/master/career_docs/Adarsh_Das_CV.pdf");
-                        ui.separator();
-                        ui.add_space(10.0);
-
-                        ui.heading(add_text(TextType::SubHeading, "Linguistic Proficiency"));
-                        ui.label(add_text(TextType::Paragraph, "English (A1)"));
-                        ui.label(add_text(TextType::Paragraph, "German (B2)"));
-                        ui.label(add_text(TextType::Paragraph, "Hindi (Native)"));
-                        ui.add_space(10.0);
-                        ui.separator();
-
-                        ui.add_space(10.0);
-                        ui.heading(add_text(TextType::SubHeading, "Misc."));
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Favorite Fungi: Spongiforma squarepantsii"), "https://en.wikipedia.org/wiki/Spongiforma_squarepantsii");
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Favorite Insect: Aha ha"), "https://en.wikipedia.org/wiki/Aha_ha");
+                        for (index, section) in content.sidebar.iter().enumerate() {
+                            if index > 0 {
+                                ui.add_space(10.0);
+                                ui.separator();
+                                ui.add_space(10.0);
+                            }
+                            ui.heading(add_text(TextType::SubHeading, section.localized_title()));
+                            self.add_section_body(ui, content, section);
+                        }
                     });
                 });
             });
         }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
+        // Headings move while scrolling to a target, so the heading in view is only known next frame.
+        #[cfg(target_arch = "wasm32")]
+        let navigating = self.scroll_target.is_some();
+        let mut clicked_timeline_item = None;
+        let mut central_panel = egui::CentralPanel::default();
+        if self.reader_mode {
+            central_panel = central_panel.frame(reader::frame(&ctx.style()));
+        }
+        central_panel.show(ctx, |ui| {
             egui_extras::install_image_loaders(ctx);
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                ui.vertical(|ui| {
-                    //
-                    // About Me
-                    //
-                    // ui.set_max_width(ui.available_width() * 0.5);
-                    let response = ui.heading(add_text(TextType::Heading, "About Me"));
-                    if let Some(about_me_button) = about_me_button {
-                        if about_me_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    ui.horizontal_wrapped(|ui| {
-                        // ui.set_max_width(ui.available_width() * 0.5);
-                        ui.label(add_text(TextType::Paragraph, "Hi, I am"));
-                        // pronunciation: [ɑːˈd̪ɐɾ.ɕ/]
-                        ui.label(add_text(TextType::Paragraph, "Adarsh Das").underline());
-                        ui.label(add_text(TextType::Paragraph, ", a third year undergraduate student at BITS Pilani, Hyderabad Campus. I am a passionate programmer and a tech enthusiast. I am also a member of the"));
-                    });
-                    ui.label(add_text(TextType::Paragraph, "Furthermore, I am self-motivated, enthusiastic, reliable and a responsible team-spirited person with a strong foundation in ethics."));
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!self.reader_mode, |ui| {
+                    ui.selectable_value(
+                        &mut self.layout,
+                        Layout::Linear,
+                        add_text(TextType::Paragraph, tr("layout.page")),
+                    );
+                    ui.selectable_value(
+                        &mut self.layout,
+                        Layout::Timeline,
+                        add_text(TextType::Paragraph, tr("layout.timeline")),
+                    );
+                    ui.selectable_value(
+                        &mut self.layout,
+                        Layout::Desktop,
+                        add_text(TextType::Paragraph, tr("layout.desktop")),
+                    );
+                });
+                ui.separator();
+                ui.toggle_value(
+                    &mut self.reader_mode,
+                    add_text(TextType::Paragraph, tr("reader.toggle")),
+                )
+                .on_hover_text(tr("reader.hint"));
+                #[cfg(target_arch = "wasm32")]
+                if ui
+                    .button(add_text(TextType::Paragraph, tr("reader.print")))
+                    .on_hover_text(tr("reader.print_hint"))
+                    .clicked()
+                {
+                    crate::web::print_html(&crate::export::print_document());
+                }
+            });
+            ui.separator();
 
-                    add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Résumé"), "https://github.com/Saphereye/saphereye.github.io/blob/master/career_docs/Adarsh_Das_CV.pdf");
+            if self.reader_mode {
+                reader::reader_ui(ui, content);
+                return;
+            }
+            match self.layout {
+                Layout::Timeline => clicked_timeline_item = self.timeline.ui(ui),
+                Layout::Desktop => {
+                    self.desktop.ui(ui, &mut self.apps);
+                }
+                Layout::Linear => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        self.toc.begin_frame();
+                        ui.vertical(|ui| {
+                            for section in &content.sections {
+                                let title = section.localized_title();
+                                let response =
+                                    add_heading(ui, TextType::Heading, title, &self.search_words);
+                                self.toc.register(&section.id, title, 0, &response);
+                                self.scroll_if_target(&response, &section.id);
+                                self.add_section_body(ui, content, section);
+                            }
 
-                    ui.separator();
-                    ui.add_space(10.0);
-                    //
-                    // Education
-                    //
-                    let response = ui.heading(add_text(TextType::Heading, "Education"));
-                    if let Some(education_button) = education_button {
-                        if education_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    ui.horizontal_wrapped(|ui| {
-                        ui.label(add_text(TextType::Paragraph, "Currently pursuing my"));
-                        ui.label(add_text(TextType::Paragraph, "B.E. Hons in Computer Science and Minor in Data Science").underline(),);
-                        ui.label(add_text(TextType::Paragraph, "from"));
-                        ui.label(add_text(TextType::Paragraph, "Birla Institute of Technology and Science, Hyderabad Campus.").underline(),);
+                            if window_width < 972.0 {
+                                for section in &content.sidebar {
+                                    if matches!(section.body, SectionBody::Contacts) {
+                                        ui.heading(add_text(
+                                            TextType::Heading,
+                                            section.localized_title(),
+                                        ));
+                                        self.add_section_body(ui, content, section);
+                                    }
+                                }
+                            }
+                        });
+                        self.toc.end_frame(ui.clip_rect().top());
                     });
-                    ui.label(add_text(TextType::Paragraph, "I am currently in my third year of study."));
-                    ui.add_space(10.0);
-                    ui.separator();
+                }
+            }
+        });
+        // A target that was not laid out this frame (e.g. hidden by a filter) is dropped.
+        self.scroll_target = None;
+        #[cfg(target_arch = "wasm32")]
+        if !navigating && self.layout == Layout::Linear && !self.reader_mode {
+            self.update_url_fragment();
+        }
+        if let Some(anchor) = clicked_timeline_item {
+            self.navigate(content, &anchor);
+        }
 
-                    //
-                    // Strengths
-                    //
-                    let response = ui.heading(add_text(TextType::Heading, "Strengths"));
-                    if let Some(strengths_button) = strengths_button {
-                        if strengths_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    ui.horizontal_wrapped(|ui| {
-                        let _ = ui.button(add_text(TextType::Paragraph, "Python"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "C++"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "C"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Git"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Godot"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "OpenGL"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Java"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Rust"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Sklearn"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Pandas"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Django"));
-                    });
-                    ui.add_space(10.0);
-                    ui.separator();
+        self.course_window(ctx, content);
+        self.project_window(ctx, content);
+    }
+}
 
-                    //
-                    // Coursework
-                    //
-                    let response = ui.heading(add_text(TextType::Heading, "Coursework"));
-                    if let Some(coursework_button) = coursework_button {
-                        if coursework_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    ui.horizontal_wrapped(|ui| {
-                        ui.set_max_width(ui.available_width());
-                        let _ = ui.button(add_text(TextType::Paragraph, "Data Structures and Algorithms"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Database Systems"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Object Oriented Programming"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Operating Systems"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Computer Architecture"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Deep Learning"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Machine Learning"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Artificial Intelligence"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Image Processing"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Theory of Computation"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Principles of programming languages"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Discrete Structure in Computer Science"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Logic in Computer Science"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Digital Design"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Computer Programming"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Probability and Statistics"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Linear Algebra"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Differential Calculus"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Principles of Management"));
-                        let _ = ui.button(add_text(TextType::Paragraph, "Technical Report Writing"));
+pub(crate) fn text_size(text_type: TextType) -> f32 {
+    match text_type {
+        TextType::Heading => 35.0,
+        TextType::SubHeading => 25.0,
+        TextType::Paragraph => 20.0,
+        TextType::Project => 30.0,
+    }
+}
 
-                    });
-                    ui.add_space(10.0);
-                    ui.separator();
+pub(crate) fn add_text(text_type: TextType, text: &str) -> egui::RichText {
+    egui::RichText::new(text).size(text_size(text_type))
+}
 
-                    //
-                    // Research Projects
-                    //
-                    let response = ui.heading(add_text(TextType::Heading, "Research Projects"));
-                    if let Some(research_projects_button) = research_projects_button {
-                        if research_projects_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    add_project(ui, "Chess AI comparative analysis", "Aimed to explore search algorithms to create a novel chess engine. We use python3.10 programming language and chess module as an interace for handling the board. Furthermore chessboard library was used for gui display.", Some("https://github.com/Saphereye/ChessAI"), Some(egui::include_image!("../assets/projects/chess.png")));
-                    add_project(ui, "Malaria Cell classification using state-of-the-art Vision Tranformer", "The project utilized vision transformer trained on various processed images of the training data such as green channel, green channle canny filx`tered and klahe filter. The individual models where then combined using a ensemble methods. The validation set gave 99.7% accuracy and the testing accuracy was ~94%", None, Some(egui::include_image!("../assets/projects/ip.png")));
-                    add_project(ui, "Pneumonia diagnosis using chest X-ray", "The project leveraged vision transformers architecture for pneumonia diagnosis. The project also included implementing methods for improving upon the research paper on which it was implemented", None, Some(egui::include_image!("../assets/projects/dl.png")));
-                    //
-                    // Work Experience, Competetions and Club activities
-                    //
-                    let response = ui.heading(add_text(TextType::Heading, "Work Experience, Competetions and Club activities"));
-                    if let Some(work_experience_button) = work_experience_button {
-                        if work_experience_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    add_project(ui, "BC6 data analysis", "This was a project for my research internship at NCPOR, Goa. The project was made using Django. It supports a step by step research submission portal and features such as email verification for proposal acceptance. It also includes a page for visualizing BC6 carbon data.", Some("https://github.com/Saphereye/ncpor-portal-ps2"), Some(egui::include_image!("../assets/projects/data.png")));
-                    add_project(ui, "ServiQuick: One touch emergency services app", "ServiQuik is a user-friendly mobile application designed to provide swift access to emergency services. With just a few taps, you can call for immediate assistance from hospitals, fire stations, or the police. The app employs Text-to-Speech (TTS) technology to convey essential information about the nearest service of your choice and provides a convenient route on the map for your destination.", Some("https://github.com/Saphereye/ServiQuick"), Some(egui::include_image!("../assets/projects/serviquick.png")));
-                    add_project(ui, "Dockerized E-Commerce with Spring Security and React.js", "A ready-to-scale, docker-ized web application that supported placing orders, order status, inventory management, Admin, Manager, and User functionality using and authentication using Spring Security and MySQL coupled with a React.js based frontend. Utilized a custom-made system for user payments and had coupon code functionality and email notification", Some("https://github.com/Divyateja04/Oopsie_BITS_CSF213"), Some(egui::include_image!("../assets/projects/oopsie.png")));
-                    add_project(ui, "Graph Based Database Architecture in Multithreaded C", "A C based multithreaded graph database system which supported multiple concurrent requests for writing, reading and traversing the graphs in the database using DFS and BFS", Some("https://github.com/Divyateja04/ClientServer_CSF372"), None);
-                    add_project(ui, "Handouts For You", "A dedicated website to facilitate the accessibility of almost 2000+ course handouts, expanded the website's functionality by incorporating features for sharing notes, resources, and questions related to the campus, and implemented a course review system, notes sharing system and CGPA cutoff system with 4000+ students handling 1000+ requests per day.", Some("https://handoutsforyou.vercel.app/"), Some(egui::include_image!("../assets/projects/handouts.png")));
-                    add_project(ui, "Secure and friendly Quiz App", "A secure and user friendly quiz app interface that detects malpractices and auto submits the user's response. It can detect tab changes and window changes as well.z", Some("https://quizapp-ten-swart.vercel.app/"), Some(egui::include_image!("../assets/projects/quizapp.png")));
-                    add_project(ui, "Article: Free Software Movement and its Importance in the Modern World", "In today’s digital world, every aspect of our lives is intertwined with computers. A “computer” will play a considerable part in official work or leisure activities. With such a significant dependence on this technology, the idea of not having control over what we use is absurd. This is, sadly, the current situation with proprietary software. This article explores this problem in detail", Some("https://csabitsh.wordpress.com/2022/10/15/free-software-movement-and-its-importance-in-the-modern-world/"), Some(egui::include_image!("../assets/projects/fsm.webp")));
-                    add_project(ui, "Article: A Brief History of Computer Graphics", "Millions of people watch movies every year, marveling at the impeccable CGI (Computer-generated imagery). According to some studies, teens use their phones for an average of about 8 hours a week, surfing social media and popular websites like YouTube. Knowingly or unknowingly, computer graphics is inherent everywhere around us. Consumers often overlook how much computer graphics is part of their lives, from bringing their favorite characters to life to providing realistic simulations. This article explores this topic in detail", Some("https://csabitsh.wordpress.com/a-brief-history-of-computer-graphics/"), Some(egui::include_image!("../assets/projects/graphics2.jpg")));
-
-                    //
-                    // Hobby projects
-                    //
-                    let response = ui.heading(add_text(TextType::Heading, "Hobby Projects"));
-                    if let Some(personal_projects_button) = personal_projects_button {
-                        if personal_projects_button.clicked() {
-                            response.scroll_to_me(Some(egui::Align::Min));
-                        }
-                    }
-                    add_project(ui, "Image display on terminal", "This program addresses the challenge of displaying images in a terminal, which lacks the ability to render small pixels. It achieves this by pixelating the image and leveraging the terminal's color coding capabilities to provide a more accurate representation", Some("https://github.com/Saphereye/image-to-terminal"), Some(egui::include_image!("../assets/projects/imgterm.png")));
-                    add_project(
-                        ui,
-                        "Brainfuck Interpreter", 
-                        "Implemented a brainf*ck interpreter in Rust with the brain of the code in about 150 loc. Supports intuitive command line support. A toy project finished in two hours.\nThe project support improved versions also.\nAs a demo, for the below input", 
-                        Some("https://github.com/Saphereye/brainfuck-interpreter"), 
-                        Some(egui::include_image!("../assets/projects/brainfuck.png")
-                    ));
-                    add_project(ui, "Multipurpose Telegram Bot", "A personal telegram bot implemeted using teloxide library in rust. Supports a wide variety of toy features such as reporting the weather and sending cat pics. Sends a greeting at 8am everyday and can also jot down todos for every user.", Some("https://github.com/Saphereye/herr-jr"), Some(egui::include_image!("../assets/projects/herrjr.png")));
-                    add_project(ui, "NES Emulator", "Implemented a an NES emulator in rust. Supports screen switching and input mapping.", Some("https://github.com/Saphereye/nes_emulator"), Some(egui::include_image!("../assets/projects/nes.png")));
-                    add_project(ui, "Chip-8 Emulator", "Complete implementation of a Chip-8 (fantasy video game console) emulator using Rust.", Some("https://github.com/Saphereye/chip-8-emulator"), Some(egui::include_image!("../assets/projects/chip8.png")));
-                    // add_project(ui, "Lan based chatting application", "", Some("https://github.com/Saphereye/lan-chat"), None);  
-
-                    if window_width < 972.0 {
-                        ui.heading(add_text(TextType::Heading, "Contact Me"));
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Email: adarshdas950@gmail.com"), "mailto:adarshdas950@gmail.com");
-                        ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Phone Number: +91 85278 59660"), "tel:+918527859660");
-                        ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Github"), "https://github.com/Saphereye");
-                        ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Linkedin"), "https://www.linkedin.com/in/adarsh-das-8684ab240/");
-                        ui.add_space(10.0);
-                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, "Résumé"), "https://github.com/Saphereye/saphereye.github.io/blob/master/career_docs/Adarsh_Das_CV.pdf");
-                    }
-                });
-            })
-        });
+/// Like [`add_text`], with the words matching the search query highlighted.
+fn add_highlighted_text(
+    ui: &egui::Ui,
+    text_type: TextType,
+    text: &str,
+    search_words: &[String],
+) -> egui::WidgetText {
+    let ranges = search::match_ranges(text, search_words);
+    if ranges.is_empty() {
+        return add_text(text_type, text).into();
     }
+
+    let normal = egui::TextFormat {
+        font_id: egui::FontId::proportional(text_size(text_type)),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let mut job = egui::text::LayoutJob::default();
+    append_highlighted(ui, &mut job, text, normal, search_words);
+    job.into()
 }
 
-fn add_text(text_type: TextType, text: &str) -> egui::RichText {
-    match text_type {
-        TextType::Heading => egui::RichText::new(text).size(35.0),
-        TextType::SubHeading => egui::RichText::new(text).size(25.0),
-        TextType::Paragraph => egui::RichText::new(text).size(20.0),
-        TextType::Project => egui::RichText::new(text).size(30.0),
+/// Appends the text in the given format, with the words matching the search query highlighted.
+pub(crate) fn append_highlighted(
+    ui: &egui::Ui,
+    job: &mut egui::text::LayoutJob,
+    text: &str,
+    format: egui::TextFormat,
+    search_words: &[String],
+) {
+    let visuals = ui.visuals();
+    let highlighted = egui::TextFormat {
+        color: visuals.strong_text_color(),
+        background: visuals.selection.bg_fill,
+        ..format.clone()
+    };
+    let mut end = 0;
+    for range in search::match_ranges(text, search_words) {
+        job.append(&text[end..range.start], 0.0, format.clone());
+        job.append(&text[range.clone()], 0.0, highlighted.clone());
+        end = range.end;
+    }
+    job.append(&text[end..], 0.0, format);
+}
+
+/// A heading with the words matching the search query highlighted.
+fn add_heading(
+    ui: &mut egui::Ui,
+    text_type: TextType,
+    text: &str,
+    search_words: &[String],
+) -> egui::Response {
+    match add_highlighted_text(ui, text_type, text, search_words) {
+        egui::WidgetText::RichText(text) => ui.heading(text),
+        highlighted => ui.label(highlighted),
     }
 }
 
-fn add_custom_hyperlink(ui: &mut egui::Ui, text: egui::RichText, url: &str) -> egui::Response {
+pub(crate) fn add_custom_hyperlink(
+    ui: &mut egui::Ui,
+    text: egui::RichText,
+    url: &str,
+) -> egui::Response {
     ui.add(egui::Hyperlink::from_label_and_url(text, url).open_in_new_tab(true))
 }
 
-fn add_project(
+pub(crate) fn add_paragraph(ui: &mut egui::Ui, paragraph: &Paragraph, search_words: &[String]) {
+    match paragraph {
+        Paragraph::Plain(text) => {
+            ui.label(add_highlighted_text(
+                ui,
+                TextType::Paragraph,
+                text,
+                search_words,
+            ));
+        }
+        Paragraph::Spans(spans) => {
+            ui.horizontal_wrapped(|ui| {
+                for span in spans {
+                    let text = add_text(TextType::Paragraph, &span.text);
+                    match &span.link {
+                        Some(link) => add_custom_hyperlink(ui, text, link),
+                        None if span.underline => ui.label(text.underline()),
+                        None => ui.label(add_highlighted_text(
+                            ui,
+                            TextType::Paragraph,
+                            &span.text,
+                            search_words,
+                        )),
+                    };
+                }
+            });
+        }
+    }
+}
+
+/// Returns the response of the project heading, and whether the project's
+/// detail window was asked for.
+pub(crate) fn add_project(
     ui: &mut egui::Ui,
-    name: &str,
-    description: &str,
-    source_code_link: Option<&str>,
-    image: Option<egui::ImageSource<'_>>,
-) {
-    // Image display on terminal
+    project: &Project,
+    search_words: &[String],
+) -> (egui::Response, bool) {
     ui.add_space(10.0);
-    if let Some(source_code_link) = source_code_link {
-        ui.hyperlink_to(add_text(TextType::Project, name), source_code_link);
+    let heading = if let Some(link) = project.main_link() {
+        let name = add_highlighted_text(ui, TextType::Project, &project.name, search_words);
+        ui.hyperlink_to(name, link)
     } else {
-        ui.heading(add_text(TextType::Project, name));
-    }
+        add_heading(ui, TextType::Project, &project.name, search_words)
+    };
+
+    add_project_details(ui, project);
     ui.add_space(10.0);
-    ui.label(add_text(TextType::Paragraph, description));
+    markdown::markdown_ui(
+        ui,
+        &project.id,
+        &project.description,
+        TextType::Paragraph,
+        search_words,
+    );
     ui.add_space(10.0);
-    if let Some(image) = image {
-        ui.add(egui::Image::new(image).fit_to_exact_size(egui::Vec2 {
-            x: ui.available_width(),
-            y: 500.0,
-        }));
+
+    if project.links_count() > 1 {
+        add_project_links(ui, project);
+        ui.add_space(10.0);
+    }
+
+    let mut open_details = false;
+    if let Some(thumbnail) = project.images.first() {
+        open_details |= images::lazy_image(
+            ui,
+            &thumbnail.path,
+            egui::vec2(ui.available_width(), 250.0),
+            egui::Sense::click(),
+        )
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .on_hover_text(tr("project.show_details"))
+        .clicked();
+    }
+    if project.images.len() > 1 {
+        let label = tr_with(
+            "project.view_images",
+            &[("count", &project.images.len().to_string())],
+        );
+        open_details |= ui.link(add_text(TextType::Paragraph, &label)).clicked();
     }
     ui.add_space(10.0);
     ui.separator();
+    (heading, open_details)
+}
+
+/// Everything about a project: details, description, links and its images.
+pub(crate) fn project_details_ui(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    project: &Project,
+    gallery: &mut Gallery,
+    search_words: &[String],
+) {
+    add_project_details(ui, project);
+    ui.add_space(10.0);
+    markdown::markdown_ui(
+        ui,
+        id_source,
+        &project.description,
+        TextType::Paragraph,
+        search_words,
+    );
+    ui.add_space(10.0);
+    add_project_links(ui, project);
+    ui.add_space(10.0);
+    gallery.ui(ui, &project.images);
+}
+
+/// Role, duration, status and technologies of the project.
+fn add_project_details(ui: &mut egui::Ui, project: &Project) {
+    let mut details: Vec<String> = project.role.iter().cloned().collect();
+    details.extend(project.duration());
+    match project.status {
+        Status::Completed => {}
+        Status::Ongoing => details.push(tr("project.ongoing").to_owned()),
+        Status::Archived => details.push(tr("project.archived").to_owned()),
+    }
+    if !details.is_empty() {
+        ui.label(add_text(TextType::Paragraph, &details.join(" · ")).weak());
+    }
+
+    if !project.tech.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            for tech in &project.tech {
+                ui.label(add_text(TextType::Paragraph, tech).small().code());
+            }
+        });
+    }
+}
+
+fn add_project_links(ui: &mut egui::Ui, project: &Project) {
+    ui.horizontal_wrapped(|ui| {
+        for (label, link) in [
+            (tr("project.source"), &project.links.source),
+            (tr("project.demo"), &project.links.demo),
+            (tr("project.paper"), &project.links.paper),
+        ] {
+            if let Some(link) = link {
+                add_custom_hyperlink(ui, add_text(TextType::Paragraph, label), link);
+            }
+        }
+    });
 }
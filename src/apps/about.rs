@@ -0,0 +1,40 @@
+use crate::app::{add_paragraph, add_text, TextType};
+use crate::contact::{ContactCard, ContactStyle};
+use crate::content::{SectionBody, CONTENT};
+use crate::i18n::tr;
+
+/// The text sections of the portfolio with the contact details.
+#[derive(Default)]
+pub struct About {
+    contact_card: ContactCard,
+}
+
+impl super::MiniApp for About {
+    fn id(&self) -> &'static str {
+        "about"
+    }
+
+    fn title(&self) -> &'static str {
+        tr("window.about")
+    }
+
+    fn icon(&self) -> &'static str {
+        "👤"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        let content = &*CONTENT;
+        for section in content.sections.iter().chain(&content.sidebar) {
+            let SectionBody::Text { paragraphs, .. } = &section.body else {
+                continue;
+            };
+            ui.heading(add_text(TextType::SubHeading, section.localized_title()));
+            for paragraph in paragraphs {
+                add_paragraph(ui, paragraph, &[]);
+            }
+            ui.add_space(10.0);
+        }
+        ui.separator();
+        self.contact_card.ui(ui, content, ContactStyle::Full);
+    }
+}
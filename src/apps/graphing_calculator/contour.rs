@@ -0,0 +1,271 @@
+//! Tracing relations between `x` and `y` over a grid: the curve where
+//! `F(x, y) = 0` by marching squares, and the region where an inequality
+//! holds as rectangles of whole cells.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// An edge of the grid, by the corner at its lower or left end.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Edge {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+/// The values of `F` at the corners of a grid of cells.
+pub struct Grid {
+    min: [f64; 2],
+    /// Size of a cell.
+    step: [f64; 2],
+    columns: usize,
+    rows: usize,
+    /// Row by row from the bottom, `columns + 1` values each.
+    values: Vec<f64>,
+}
+
+impl Grid {
+    /// Evaluates `f` at the corners of `columns × rows` cells between `min` and `max`.
+    pub fn new(
+        f: &mut impl FnMut(f64, f64) -> f64,
+        min: [f64; 2],
+        max: [f64; 2],
+        [columns, rows]: [usize; 2],
+    ) -> Self {
+        let step = [
+            (max[0] - min[0]) / columns as f64,
+            (max[1] - min[1]) / rows as f64,
+        ];
+        let mut grid = Self {
+            min,
+            step,
+            columns,
+            rows,
+            values: Vec::with_capacity((columns + 1) * (rows + 1)),
+        };
+        for row in 0..=rows {
+            for column in 0..=columns {
+                let [x, y] = grid.corner(column, row);
+                grid.values.push(f(x, y));
+            }
+        }
+        grid
+    }
+
+    fn corner(&self, column: usize, row: usize) -> [f64; 2] {
+        [
+            self.min[0] + column as f64 * self.step[0],
+            self.min[1] + row as f64 * self.step[1],
+        ]
+    }
+
+    fn value(&self, column: usize, row: usize) -> f64 {
+        self.values[row * (self.columns + 1) + column]
+    }
+
+    /// The corners at the ends of the edge.
+    fn ends(edge: Edge) -> [(usize, usize); 2] {
+        match edge {
+            Edge::Horizontal(column, row) => [(column, row), (column + 1, row)],
+            Edge::Vertical(column, row) => [(column, row), (column, row + 1)],
+        }
+    }
+
+    /// Whether `F` changes sign along the edge.
+    fn crosses(&self, edge: Edge) -> bool {
+        let [(c0, r0), (c1, r1)] = Self::ends(edge);
+        (self.value(c0, r0) > 0.0) != (self.value(c1, r1) > 0.0)
+    }
+
+    /// Where along the edge `F` is zero, interpolating linearly.
+    fn zero_on(&self, edge: Edge) -> [f64; 2] {
+        let [(c0, r0), (c1, r1)] = Self::ends(edge);
+        let (v0, v1) = (self.value(c0, r0), self.value(c1, r1));
+        let t = v0 / (v0 - v1);
+        let (a, b) = (self.corner(c0, r0), self.corner(c1, r1));
+        [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+    }
+
+    /// Whether `F` is near zero where the edge is crossed, rather than
+    /// changing sign by jumping through infinity, as `y - tan x` does.
+    fn is_root(&self, edge: Edge, f: &mut impl FnMut(f64, f64) -> f64) -> bool {
+        let [(c0, r0), (c1, r1)] = Self::ends(edge);
+        let largest = self.value(c0, r0).abs().max(self.value(c1, r1).abs());
+        let [x, y] = self.zero_on(edge);
+        f(x, y).abs() <= largest / 4.0
+    }
+
+    /// The curves where `F` is zero, as polylines.
+    ///
+    /// `f` is evaluated again within cells, to tell apart the two ways a
+    /// cell with all four edges crossed can be cut, and at the crossings.
+    pub fn contour(&self, f: &mut impl FnMut(f64, f64) -> f64) -> Vec<Vec<[f64; 2]>> {
+        let mut segments: Vec<[Edge; 2]> = Vec::new();
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let corners = [
+                    self.value(column, row),
+                    self.value(column + 1, row),
+                    self.value(column + 1, row + 1),
+                    self.value(column, row + 1),
+                ];
+                if corners.iter().any(|value| !value.is_finite()) {
+                    continue;
+                }
+                let [bottom, right, top, left] = [
+                    Edge::Horizontal(column, row),
+                    Edge::Vertical(column + 1, row),
+                    Edge::Horizontal(column, row + 1),
+                    Edge::Vertical(column, row),
+                ];
+                let crossed: Vec<Edge> = [bottom, right, top, left]
+                    .into_iter()
+                    .filter(|edge| self.crosses(*edge))
+                    .collect();
+                let pairs = match crossed.as_slice() {
+                    [a, b] => vec![[*a, *b]],
+                    [_, _, _, _] => {
+                        let [x, y] = self.corner(column, row);
+                        let centre = f(x + self.step[0] / 2.0, y + self.step[1] / 2.0);
+                        // Keep the corners with the centre's sign joined through the middle.
+                        if (centre > 0.0) == (corners[0] > 0.0) {
+                            vec![[bottom, right], [top, left]]
+                        } else {
+                            vec![[bottom, left], [top, right]]
+                        }
+                    }
+                    _ => Vec::new(),
+                };
+                for [a, b] in pairs {
+                    if self.is_root(a, f) && self.is_root(b, f) {
+                        segments.push([a, b]);
+                    }
+                }
+            }
+        }
+        self.join(&segments)
+    }
+
+    /// Joins segments that share an edge into polylines.
+    fn join(&self, segments: &[[Edge; 2]]) -> Vec<Vec<[f64; 2]>> {
+        let mut at_edge: HashMap<Edge, Vec<usize>> = HashMap::new();
+        for (index, [a, b]) in segments.iter().enumerate() {
+            at_edge.entry(*a).or_default().push(index);
+            at_edge.entry(*b).or_default().push(index);
+        }
+        let mut used = vec![false; segments.len()];
+        let mut lines = Vec::new();
+        for start in 0..segments.len() {
+            if used[start] {
+                continue;
+            }
+            used[start] = true;
+            let [first, second] = segments[start];
+            // Walk from either end of the segment as far as the curve goes.
+            let mut halves = [vec![first], vec![second]];
+            for (half, from) in halves.iter_mut().zip([first, second]) {
+                let mut edge = from;
+                while let Some(&next) = at_edge[&edge].iter().find(|&&index| !used[index]) {
+                    used[next] = true;
+                    let [a, b] = segments[next];
+                    edge = if a == edge { b } else { a };
+                    half.push(edge);
+                }
+            }
+            let [mut backward, forward] = halves;
+            backward.reverse();
+            backward.extend(forward);
+            lines.push(
+                backward
+                    .into_iter()
+                    .map(|edge| self.zero_on(edge))
+                    .collect(),
+            );
+        }
+        lines
+    }
+
+    /// The cells where `inside` holds for the mean of their corners, merged
+    /// into as few rectangles as rows allow, each as its lower left and upper
+    /// right corners.
+    pub fn region(&self, inside: impl Fn(f64) -> bool) -> Vec<[[f64; 2]; 2]> {
+        let mut rectangles = Vec::new();
+        // Runs of columns still being extended upwards, with the row they start at.
+        let mut open: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for row in 0..=self.rows {
+            let mut runs = BTreeMap::new();
+            let mut run_start = None;
+            for column in 0..=self.columns {
+                let is_inside = row < self.rows && column < self.columns && {
+                    let mean = (self.value(column, row)
+                        + self.value(column + 1, row)
+                        + self.value(column, row + 1)
+                        + self.value(column + 1, row + 1))
+                        / 4.0;
+                    mean.is_finite() && inside(mean)
+                };
+                match (is_inside, run_start) {
+                    (true, None) => run_start = Some(column),
+                    (false, Some(start)) => {
+                        let run = (start, column);
+                        runs.insert(run, open.remove(&run).unwrap_or(row));
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+            for ((start, end), first_row) in std::mem::replace(&mut open, runs) {
+                rectangles.push([self.corner(start, first_row), self.corner(end, row)]);
+            }
+        }
+        rectangles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circle(x: f64, y: f64) -> f64 {
+        x * x + y * y - 1.0
+    }
+
+    #[test]
+    fn traces_a_circle_as_one_closed_line() {
+        let grid = Grid::new(&mut circle, [-2.0, -2.0], [2.0, 2.0], [40, 40]);
+        let lines = grid.contour(&mut circle);
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert!(line.len() > 20, "only {} points", line.len());
+        assert_eq!(line.first(), line.last());
+        for [x, y] in line {
+            let radius = x.hypot(*y);
+            assert!((radius - 1.0).abs() < 0.01, "({x}, {y}) is off the circle");
+        }
+    }
+
+    #[test]
+    fn skips_sign_changes_through_infinity() {
+        let mut f = |x: f64, y: f64| y - x.tan();
+        let grid = Grid::new(&mut f, [-3.0, -3.0], [3.0, 3.0], [60, 60]);
+        let lines = grid.contour(&mut f);
+        // One branch of tan for each period in view, and no line across the asymptotes.
+        assert_eq!(lines.len(), 3);
+        for [x, y] in lines.iter().flatten() {
+            assert!((y - x.tan()).abs() < 0.1, "({x}, {y}) is off the curve");
+        }
+    }
+
+    #[test]
+    fn fills_the_inside_of_a_circle() {
+        let grid = Grid::new(&mut circle, [-2.0, -2.0], [2.0, 2.0], [80, 80]);
+        let rectangles = grid.region(|value| value < 0.0);
+        let area: f64 = rectangles
+            .iter()
+            .map(|[min, max]| (max[0] - min[0]) * (max[1] - min[1]))
+            .sum();
+        assert!((area - std::f64::consts::PI).abs() < 0.1, "area {area}");
+        for [min, max] in &rectangles {
+            assert!(min[0] < max[0] && min[1] < max[1]);
+            assert!(min[0] >= -1.1 && max[0] <= 1.1 && min[1] >= -1.1 && max[1] <= 1.1);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Drawing the graphs into the plot.
+
+use egui_plot::{Line, LineStyle, PlotBounds, PlotPoints, PlotUi, Points, Polygon};
+
+use super::contour::Grid;
+use super::expr::Expr;
+use super::graph::Graph;
+use super::Expression;
+
+/// Samples per point of the plot's width, for functions of `x`.
+const SAMPLES_PER_POINT: f32 = 1.5;
+/// Samples along the range of `t` or `θ`.
+const PARAMETER_SAMPLES: usize = 2000;
+/// Width and height of a cell of the grid relations are traced over, in points.
+const CELL_SIZE: f32 = 4.0;
+/// Opacity of the region where an inequality holds.
+const REGION_OPACITY: f32 = 0.25;
+/// Radius of the point where a tangent touches its graph, in points.
+const TANGENT_POINT_RADIUS: f32 = 4.0;
+
+/// Draws the graph of the expression into a plot of `size` points, given
+/// the values of the free variables.
+pub(super) fn draw(
+    plot_ui: &mut PlotUi,
+    expression: &Expression,
+    graph: &Graph,
+    variables: &[(&str, f64)],
+    size: egui::Vec2,
+) {
+    let bounds = plot_ui.plot_bounds();
+    // The graph's own variables go first, to be set for each point.
+    let mut values: Vec<(&str, f64)> = graph
+        .own_variables()
+        .iter()
+        .map(|name| (*name, 0.0))
+        .chain(variables.iter().copied())
+        .collect();
+    let mut eval = |expr: &Expr, own: &[f64]| {
+        for (value, own) in values.iter_mut().zip(own) {
+            value.1 = *own;
+        }
+        expr.eval(&values)
+    };
+    let line = |points: Vec<[f64; 2]>| {
+        Line::new(PlotPoints::new(points))
+            .name(expression.legend_name())
+            .color(expression.color)
+            .width(2.0)
+    };
+
+    let [start, end] = expression.parameter_range;
+    let curves = match graph {
+        Graph::Function(f) => {
+            // Beyond the visible range too, so that panning does not reveal
+            // the ends before the graph is sampled again.
+            let width = bounds.width();
+            let range = [bounds.min()[0] - width / 2.0, bounds.max()[0] + width / 2.0];
+            let count = (size.x * SAMPLES_PER_POINT * 2.0) as usize;
+            let derivatives = expression.derivatives.iter().flatten();
+            let styles = [LineStyle::dashed_dense(), LineStyle::dotted_dense()];
+            for (order, (derivative, style)) in derivatives.zip(styles).enumerate() {
+                if !expression.show_derivatives[order] {
+                    continue;
+                }
+                let name = expression.derivative_name(order + 1).unwrap_or_default();
+                for points in sample_curve(|x| [x, eval(derivative, &[x])], range, count, &bounds) {
+                    plot_ui.line(line(points).name(&name).style(style));
+                }
+            }
+            if let Some(([x, y], slope)) = expression.tangent(variables) {
+                // Across the range, or only as far as stays within ten heights of the view.
+                let reach = (range[1] - range[0]).min(10.0 * bounds.height() / slope.abs());
+                let tangent = |at: f64| [at, y + slope * (at - x)];
+                plot_ui.line(line(vec![tangent(x - reach), tangent(x + reach)]).width(1.0));
+                plot_ui.points(
+                    Points::new(vec![[x, y]])
+                        .name(expression.legend_name())
+                        .color(expression.color)
+                        .radius(TANGENT_POINT_RADIUS),
+                );
+            }
+            sample_curve(|x| [x, eval(f, &[x])], range, count, &bounds)
+        }
+        Graph::Parametric(x, y) => sample_curve(
+            |t| [eval(x, &[t]), eval(y, &[t])],
+            [start, end],
+            PARAMETER_SAMPLES,
+            &bounds,
+        ),
+        Graph::Polar(r) => sample_curve(
+            |theta| {
+                let r = eval(r, &[theta]);
+                [r * theta.cos(), r * theta.sin()]
+            },
+            [start, end],
+            PARAMETER_SAMPLES,
+            &bounds,
+        ),
+        Graph::Relation(left, relation, right) => {
+            let mut f = |x, y| eval(left, &[x, y]) - eval(right, &[x, y]);
+            let cells = [
+                ((size.x / CELL_SIZE) as usize).max(1),
+                ((size.y / CELL_SIZE) as usize).max(1),
+            ];
+            let grid = Grid::new(&mut f, bounds.min(), bounds.max(), cells);
+            let style = if relation.is_strict() {
+                LineStyle::dashed_loose()
+            } else {
+                LineStyle::Solid
+            };
+            // Lines before regions, as the legend takes the colour of the first item of a name.
+            for points in grid.contour(&mut f) {
+                plot_ui.line(line(points).style(style));
+            }
+            if relation.is_inequality() {
+                let fill = expression.color.gamma_multiply(REGION_OPACITY);
+                for [[x0, y0], [x1, y1]] in grid.region(|difference| relation.holds(difference)) {
+                    plot_ui.polygon(
+                        Polygon::new(PlotPoints::new(vec![
+                            [x0, y0],
+                            [x1, y0],
+                            [x1, y1],
+                            [x0, y1],
+                        ]))
+                        .name(expression.legend_name())
+                        .fill_color(fill)
+                        .stroke(egui::Stroke::NONE),
+                    );
+                }
+            }
+            return;
+        }
+    };
+    for points in curves {
+        plot_ui.line(line(points));
+    }
+}
+
+/// Points of the curve `f(t)` for `t` across `range`, split where it is
+/// undefined or jumps. Points far outside the bounds are pulled in, as they
+/// look the same and are still drawable.
+fn sample_curve(
+    mut f: impl FnMut(f64) -> [f64; 2],
+    [start, end]: [f64; 2],
+    count: usize,
+    bounds: &PlotBounds,
+) -> Vec<Vec<[f64; 2]>> {
+    let ([min_x, min_y], [max_x, max_y]) = (bounds.min(), bounds.max());
+    let (width, height) = (max_x - min_x, max_y - min_y);
+    let pull_in = |[x, y]: [f64; 2]| {
+        [
+            x.clamp(min_x - 10.0 * width, max_x + 10.0 * width),
+            y.clamp(min_y - 10.0 * height, max_y + 10.0 * height),
+        ]
+    };
+
+    let mut curves = Vec::new();
+    let mut curve = Vec::new();
+    let mut previous: Option<(f64, [f64; 2])> = None;
+    for i in 0..=count {
+        let t = start + (end - start) * i as f64 / count as f64;
+        let point = f(t);
+        if !point.iter().all(|value| value.is_finite()) {
+            if !curve.is_empty() {
+                curves.push(std::mem::take(&mut curve));
+            }
+            previous = None;
+            continue;
+        }
+        if let Some((previous_t, [x, y])) = previous {
+            // A long step whose midpoint is not between its ends is a discontinuity, as in tan(x).
+            if (point[0] - x).abs() > width || (point[1] - y).abs() > height {
+                let [mx, my] = f((previous_t + t) / 2.0);
+                let between = |value: f64, a: f64, b: f64| (a.min(b)..=a.max(b)).contains(&value);
+                if !(between(mx, x, point[0]) && between(my, y, point[1])) {
+                    curves.push(std::mem::take(&mut curve));
+                }
+            }
+        }
+        curve.push(pull_in(point));
+        previous = Some((t, point));
+    }
+    if !curve.is_empty() {
+        curves.push(curve);
+    }
+    curves
+}
@@ -0,0 +1,283 @@
+//! Expressions of the graphing calculator and their evaluation.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// A parsed expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Constant(Constant),
+    Variable(String),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Function, Box<Expr>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constant {
+    Pi,
+    Tau,
+    E,
+}
+
+impl Constant {
+    pub const ALL: [Self; 3] = [Self::Pi, Self::Tau, Self::E];
+
+    /// The names it can be written as; the first is used when printing.
+    pub fn names(self) -> &'static [&'static str] {
+        match self {
+            Self::Pi => &["π", "pi"],
+            Self::Tau => &["τ", "tau"],
+            Self::E => &["e"],
+        }
+    }
+
+    pub fn value(self) -> f64 {
+        match self {
+            Self::Pi => std::f64::consts::PI,
+            Self::Tau => std::f64::consts::TAU,
+            Self::E => std::f64::consts::E,
+        }
+    }
+}
+
+/// Functions of one argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Sec,
+    Csc,
+    Cot,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Sqrt,
+    Exp,
+    /// Natural logarithm.
+    Ln,
+    /// Logarithm to base 10.
+    Log,
+    Abs,
+    Floor,
+    Ceil,
+}
+
+impl Function {
+    pub const ALL: [Self; 19] = [
+        Self::Sin,
+        Self::Cos,
+        Self::Tan,
+        Self::Sec,
+        Self::Csc,
+        Self::Cot,
+        Self::Asin,
+        Self::Acos,
+        Self::Atan,
+        Self::Sinh,
+        Self::Cosh,
+        Self::Tanh,
+        Self::Sqrt,
+        Self::Exp,
+        Self::Ln,
+        Self::Log,
+        Self::Abs,
+        Self::Floor,
+        Self::Ceil,
+    ];
+
+    /// The names it can be written as; the first is used when printing.
+    pub fn names(self) -> &'static [&'static str] {
+        match self {
+            Self::Sin => &["sin"],
+            Self::Cos => &["cos"],
+            Self::Tan => &["tan"],
+            Self::Sec => &["sec"],
+            Self::Csc => &["csc", "cosec"],
+            Self::Cot => &["cot"],
+            Self::Asin => &["arcsin", "asin"],
+            Self::Acos => &["arccos", "acos"],
+            Self::Atan => &["arctan", "atan"],
+            Self::Sinh => &["sinh"],
+            Self::Cosh => &["cosh"],
+            Self::Tanh => &["tanh"],
+            Self::Sqrt => &["sqrt", "√"],
+            Self::Exp => &["exp"],
+            Self::Ln => &["ln"],
+            Self::Log => &["log"],
+            Self::Abs => &["abs"],
+            Self::Floor => &["floor"],
+            Self::Ceil => &["ceil"],
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        self.names()[0]
+    }
+
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            Self::Sin => x.sin(),
+            Self::Cos => x.cos(),
+            Self::Tan => x.tan(),
+            Self::Sec => x.cos().recip(),
+            Self::Csc => x.sin().recip(),
+            Self::Cot => x.tan().recip(),
+            Self::Asin => x.asin(),
+            Self::Acos => x.acos(),
+            Self::Atan => x.atan(),
+            Self::Sinh => x.sinh(),
+            Self::Cosh => x.cosh(),
+            Self::Tanh => x.tanh(),
+            Self::Sqrt => x.sqrt(),
+            Self::Exp => x.exp(),
+            Self::Ln => x.ln(),
+            Self::Log => x.log10(),
+            Self::Abs => x.abs(),
+            Self::Floor => x.floor(),
+            Self::Ceil => x.ceil(),
+        }
+    }
+}
+
+impl Expr {
+    /// The value with the given variables; NaN where it is undefined or a
+    /// variable is missing.
+    pub fn eval(&self, variables: &[(&str, f64)]) -> f64 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Constant(constant) => constant.value(),
+            Self::Variable(name) => variables
+                .iter()
+                .find(|(variable, _)| variable == name)
+                .map_or(f64::NAN, |(_, value)| *value),
+            Self::Neg(operand) => -operand.eval(variables),
+            Self::Binary(op, left, right) => {
+                let (left, right) = (left.eval(variables), right.eval(variables));
+                match op {
+                    BinaryOp::Add => left + right,
+                    BinaryOp::Sub => left - right,
+                    BinaryOp::Mul => left * right,
+                    BinaryOp::Div => left / right,
+                    BinaryOp::Pow => pow(left, right),
+                }
+            }
+            Self::Call(function, argument) => function.apply(argument.eval(variables)),
+        }
+    }
+
+    /// Adds the names of the variables it uses to `names`.
+    pub fn collect_variables(&self, names: &mut BTreeSet<String>) {
+        match self {
+            Self::Number(_) | Self::Constant(_) => {}
+            Self::Variable(name) => {
+                names.insert(name.clone());
+            }
+            Self::Neg(operand) | Self::Call(_, operand) => operand.collect_variables(names),
+            Self::Binary(_, left, right) => {
+                left.collect_variables(names);
+                right.collect_variables(names);
+            }
+        }
+    }
+}
+
+impl Expr {
+    /// How tightly it binds when printed: operands binding less tightly than
+    /// their operator need parentheses.
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Binary(BinaryOp::Add | BinaryOp::Sub, _, _) => 1,
+            Self::Binary(BinaryOp::Mul | BinaryOp::Div, _, _) => 2,
+            Self::Neg(_) => 3,
+            Self::Number(value) if *value < 0.0 => 3,
+            Self::Binary(BinaryOp::Pow, _, _) => 4,
+            _ => 5,
+        }
+    }
+
+    /// The operand printed first.
+    fn leftmost(&self) -> &Expr {
+        match self {
+            Self::Binary(BinaryOp::Mul | BinaryOp::Div | BinaryOp::Pow, left, _) => left.leftmost(),
+            _ => self,
+        }
+    }
+}
+
+/// Prints it the way it could be typed, with as few parentheses as needed,
+/// e.g. `2x·cos(x) - 1/x^2`.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Parenthesized if binding less tightly than `precedence`, and on
+        // the right of an operator also if signed, as in `x·(-2)`.
+        let left_operand = |expr: &Expr, precedence: u8| {
+            if expr.precedence() < precedence {
+                format!("({expr})")
+            } else {
+                expr.to_string()
+            }
+        };
+        let right_operand = |expr: &Expr, precedence: u8| {
+            if expr.precedence() < precedence || expr.precedence() == 3 {
+                format!("({expr})")
+            } else {
+                expr.to_string()
+            }
+        };
+        match self {
+            // Without the sign of negative zero.
+            Self::Number(value) => write!(f, "{}", value + 0.0),
+            Self::Constant(constant) => f.write_str(constant.names()[0]),
+            Self::Variable(name) => f.write_str(name),
+            Self::Neg(operand) => write!(f, "-{}", right_operand(operand, 2)),
+            Self::Call(function, argument) => write!(f, "{}({argument})", function.name()),
+            Self::Binary(op, left, right) => match op {
+                BinaryOp::Add => write!(f, "{left} + {}", right_operand(right, 2)),
+                BinaryOp::Sub => write!(f, "{left} - {}", right_operand(right, 2)),
+                // `2x`, `2 sin(x)` and `2(x + 1)` when the number comes first.
+                BinaryOp::Mul => {
+                    let separator = match (left.as_ref(), right.leftmost()) {
+                        (Self::Number(_), Self::Number(_)) => "·",
+                        (Self::Number(_), Self::Call(..)) => " ",
+                        (Self::Number(_), _) => "",
+                        _ => "·",
+                    };
+                    let (left, right) = (left_operand(left, 2), right_operand(right, 2));
+                    write!(f, "{left}{separator}{right}")
+                }
+                BinaryOp::Div => {
+                    write!(f, "{}/{}", left_operand(left, 2), right_operand(right, 4))
+                }
+                BinaryOp::Pow => {
+                    write!(f, "{}^{}", left_operand(left, 5), right_operand(right, 5))
+                }
+            },
+        }
+    }
+}
+
+/// `base` to the power `exponent`, taking odd roots of negative numbers, e.g. (-8)^(1/3) = -2.
+fn pow(base: f64, exponent: f64) -> f64 {
+    if base < 0.0 && exponent.fract() != 0.0 {
+        let reciprocal = exponent.recip();
+        if reciprocal.fract() == 0.0 && reciprocal % 2.0 != 0.0 {
+            return -(-base).powf(exponent);
+        }
+    }
+    base.powf(exponent)
+}
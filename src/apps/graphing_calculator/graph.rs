@@ -0,0 +1,86 @@
+//! The kinds of graph the calculator draws.
+
+use std::collections::BTreeSet;
+
+use super::expr::Expr;
+
+#[derive(Debug, PartialEq)]
+pub enum Graph {
+    /// `y = f(x)`.
+    Function(Expr),
+    /// `(x(t), y(t))` over a range of `t`.
+    Parametric(Expr, Expr),
+    /// `r = f(θ)` over a range of `θ`.
+    Polar(Expr),
+    /// An equation or inequality between `x` and `y`, like `x^2 + y^2 = 1`.
+    Relation(Expr, Relation, Expr),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Relation {
+    /// Whether the relation holds where the left side minus the right side is `difference`.
+    pub fn holds(self, difference: f64) -> bool {
+        match self {
+            Self::Equal => difference == 0.0,
+            Self::Less => difference < 0.0,
+            Self::LessEqual => difference <= 0.0,
+            Self::Greater => difference > 0.0,
+            Self::GreaterEqual => difference >= 0.0,
+        }
+    }
+
+    /// Whether it is an inequality, which holds across a region.
+    pub fn is_inequality(self) -> bool {
+        self != Self::Equal
+    }
+
+    /// Whether the boundary of the region is left out of it.
+    pub fn is_strict(self) -> bool {
+        matches!(self, Self::Less | Self::Greater)
+    }
+}
+
+impl Graph {
+    /// The variables the graph is drawn over, which get no slider.
+    pub fn own_variables(&self) -> &'static [&'static str] {
+        match self {
+            Self::Function(_) => &["x"],
+            Self::Parametric(..) => &["t"],
+            Self::Polar(_) => &["θ"],
+            Self::Relation(..) => &["x", "y"],
+        }
+    }
+
+    /// The parameter a curve is traced along, if it is not a function of `x` or a relation.
+    pub fn parameter(&self) -> Option<&'static str> {
+        match self {
+            Self::Parametric(..) => Some("t"),
+            Self::Polar(_) => Some("θ"),
+            Self::Function(_) | Self::Relation(..) => None,
+        }
+    }
+
+    /// Adds the names of the variables to be given values to `names`.
+    pub fn collect_free_variables(&self, names: &mut BTreeSet<String>) {
+        let mut variables = BTreeSet::new();
+        match self {
+            Self::Function(expr) | Self::Polar(expr) => expr.collect_variables(&mut variables),
+            Self::Parametric(left, right) | Self::Relation(left, _, right) => {
+                left.collect_variables(&mut variables);
+                right.collect_variables(&mut variables);
+            }
+        }
+        for name in self.own_variables() {
+            variables.remove(*name);
+        }
+        names.append(&mut variables);
+    }
+}
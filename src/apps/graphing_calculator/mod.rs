@@ -0,0 +1,529 @@
+//! A graphing calculator: type functions, curves or relations and see their
+//! graphs.
+//!
+//! Variables other than those a graph is drawn over, like `a` in `a sin(x)`,
+//! get a slider each, which can also play through its range. The graphs are
+//! sampled afresh for the visible range whenever the plot is panned or
+//! zoomed, so they stay smooth at any scale.
+//!
+//! Functions of `x` are differentiated symbolically: their first and second
+//! derivatives can be shown simplified and drawn alongside, and a click on
+//! the plot in tangent mode draws the tangent to the nearest of them.
+
+mod contour;
+mod draw;
+mod expr;
+mod graph;
+mod parse;
+mod symbolic;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use egui::Color32;
+use egui_plot::{Legend, Plot};
+
+use crate::app::{add_text, TextType};
+use crate::i18n::{tr, tr_with};
+use expr::{BinaryOp, Expr};
+use graph::Graph;
+use parse::ParseError;
+
+/// Height the plot gets when the window is too short to fill.
+const MIN_PLOT_HEIGHT: f32 = 200.0;
+/// Colours of new expressions, in turn.
+const PALETTE: [Color32; 6] = [
+    Color32::from_rgb(199, 68, 64),
+    Color32::from_rgb(45, 112, 179),
+    Color32::from_rgb(56, 140, 70),
+    Color32::from_rgb(96, 66, 166),
+    Color32::from_rgb(250, 126, 25),
+    Color32::from_rgb(0, 150, 150),
+];
+/// Seconds a playing slider takes to go from one end of its range to the other.
+const ANIMATION_SECONDS: f64 = 4.0;
+/// Marks of the first and second derivatives.
+const PRIMES: [&str; 2] = ["′", "″"];
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Expression {
+    source: String,
+    color: Color32,
+    visible: bool,
+    /// The range of `t` or `θ` that parametric and polar curves are drawn over.
+    #[serde(default = "default_parameter_range")]
+    parameter_range: [f64; 2],
+    /// Whether the first and second derivatives of a function of `x` are drawn too.
+    #[serde(default)]
+    show_derivatives: [bool; 2],
+    /// The `x` where the tangent to a function of `x` touches it.
+    #[serde(default)]
+    tangent_at: Option<f64>,
+    /// The parsed `source`, unless it has an error.
+    #[serde(skip)]
+    graph: Option<Graph>,
+    #[serde(skip)]
+    error: Option<ParseError>,
+    /// The first and second derivatives, simplified, if the graph is of a function of `x`.
+    #[serde(skip)]
+    derivatives: Option<[Expr; 2]>,
+}
+
+impl Expression {
+    fn new(source: &str, color: Color32) -> Self {
+        let mut expression = Self {
+            source: source.to_owned(),
+            color,
+            visible: true,
+            parameter_range: default_parameter_range(),
+            show_derivatives: [false; 2],
+            tangent_at: None,
+            graph: None,
+            error: None,
+            derivatives: None,
+        };
+        expression.parse();
+        expression
+    }
+
+    fn parse(&mut self) {
+        match parse::parse_graph(&self.source) {
+            Ok(graph) => {
+                self.derivatives = match &graph {
+                    Graph::Function(f) => {
+                        let first = f.derivative("x").simplified();
+                        let second = first.derivative("x").simplified();
+                        Some([first, second])
+                    }
+                    _ => None,
+                };
+                self.graph = Some(graph);
+                self.error = None;
+            }
+            Err(error) => {
+                self.graph = None;
+                self.error = Some(error);
+                self.derivatives = None;
+            }
+        }
+    }
+
+    /// `y′ = …` or `y″ = …` for the derivative of the given order, 1 or 2.
+    fn derivative_name(&self, order: usize) -> Option<String> {
+        let derivative = &self.derivatives.as_ref()?[order - 1];
+        Some(format!("y{} = {derivative}", PRIMES[order - 1]))
+    }
+
+    /// The point where the tangent touches the graph, and its slope, if
+    /// there is a tangent and the function is differentiable there.
+    fn tangent(&self, variables: &[(&str, f64)]) -> Option<([f64; 2], f64)> {
+        let (Some(Graph::Function(f)), Some([derivative, _]), Some(x)) =
+            (&self.graph, &self.derivatives, self.tangent_at)
+        else {
+            return None;
+        };
+        let (y, slope) = (
+            value_at(f, x, variables),
+            value_at(derivative, x, variables),
+        );
+        (y.is_finite() && slope.is_finite()).then_some(([x, y], slope))
+    }
+
+    /// The name in the legend: what was typed, completed to `y = …` for a bare function of `x`.
+    fn legend_name(&self) -> String {
+        let source = self.source.trim();
+        match self.graph {
+            Some(Graph::Function(_)) if !source.contains('=') => format!("y = {source}"),
+            _ => source.to_owned(),
+        }
+    }
+}
+
+fn default_parameter_range() -> [f64; 2] {
+    [0.0, std::f64::consts::TAU]
+}
+
+/// The value of a function of `x` at `x`, given the free variables.
+fn value_at(f: &Expr, x: f64, variables: &[(&str, f64)]) -> f64 {
+    let values: Vec<(&str, f64)> = std::iter::once(("x", x))
+        .chain(variables.iter().copied())
+        .collect();
+    f.eval(&values)
+}
+
+/// Rounded to three decimals, for showing.
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0 + 0.0
+}
+
+/// The value of a free variable.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Slider {
+    value: f64,
+    min: f64,
+    max: f64,
+    playing: bool,
+    /// Whether a playing slider is moving towards `max`.
+    rising: bool,
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Self {
+            value: 1.0,
+            min: -10.0,
+            max: 10.0,
+            playing: false,
+            rising: true,
+        }
+    }
+}
+
+impl Slider {
+    /// Moves a playing slider on by `dt` seconds, turning back at either end.
+    fn animate(&mut self, dt: f64) {
+        let step = (self.max - self.min) * dt / ANIMATION_SECONDS;
+        if self.rising {
+            self.value += step;
+        } else {
+            self.value -= step;
+        }
+        if self.value >= self.max {
+            self.value = self.max;
+            self.rising = false;
+        } else if self.value <= self.min {
+            self.value = self.min;
+            self.rising = true;
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct GraphingCalculator {
+    expressions: Vec<Expression>,
+    /// The free variables of the expressions, by name.
+    sliders: BTreeMap<String, Slider>,
+    /// Whether a click on the plot draws a tangent.
+    #[serde(skip)]
+    tangent_mode: bool,
+}
+
+impl Default for GraphingCalculator {
+    fn default() -> Self {
+        let mut calculator = Self {
+            expressions: vec![
+                Expression::new("sin(x)", PALETTE[0]),
+                Expression::new("a x^2 + b", PALETTE[1]),
+                Expression::new("x^2 + y^2 < 4", PALETTE[2]),
+            ],
+            sliders: BTreeMap::new(),
+            tangent_mode: false,
+        };
+        calculator.update_sliders();
+        calculator
+    }
+}
+
+impl GraphingCalculator {
+    /// Gives every free variable a slider, and removes those of variables no longer used.
+    fn update_sliders(&mut self) {
+        let mut variables = BTreeSet::new();
+        for graph in self.expressions.iter().filter_map(|e| e.graph.as_ref()) {
+            graph.collect_free_variables(&mut variables);
+        }
+        self.sliders.retain(|name, _| variables.contains(name));
+        for name in variables {
+            self.sliders.entry(name).or_default();
+        }
+    }
+
+    fn expressions_ui(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+        let variables: Vec<(&str, f64)> = self
+            .sliders
+            .iter()
+            .map(|(name, slider)| (name.as_str(), slider.value))
+            .collect();
+        for (index, expression) in self.expressions.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                let error_span = expression.error.as_ref().map(|error| error.span.clone());
+                let mut layouter = |ui: &egui::Ui, text: &str, _wrap_width: f32| {
+                    let job = highlight_error(ui, text, error_span.clone());
+                    ui.fonts(|fonts| fonts.layout_job(job))
+                };
+                ui.horizontal(|ui| {
+                    ui.color_edit_button_srgba(&mut expression.color)
+                        .on_hover_text(tr("calculator.color"));
+                    ui.checkbox(&mut expression.visible, "")
+                        .on_hover_text(tr("calculator.visible"));
+                    let button_width = ui.spacing().interact_size.y + ui.spacing().item_spacing.x;
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut expression.source)
+                            .hint_text(tr("calculator.input_hint"))
+                            .desired_width(ui.available_width() - button_width)
+                            .layouter(&mut layouter),
+                    );
+                    if response.changed() {
+                        expression.parse();
+                        changed = true;
+                    }
+                    if ui
+                        .small_button("🗑")
+                        .on_hover_text(tr("calculator.remove"))
+                        .clicked()
+                    {
+                        removed = Some(index);
+                    }
+                });
+                match &expression.error {
+                    Some(error) if !expression.source.trim().is_empty() => {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            error.message(&expression.source),
+                        );
+                    }
+                    _ => {}
+                }
+                if let Some(parameter) = expression.graph.as_ref().and_then(Graph::parameter) {
+                    let [start, end] = &mut expression.parameter_range;
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(start).speed(0.1));
+                        ui.label(add_text(TextType::Paragraph, &format!("≤ {parameter} ≤")));
+                        ui.add(egui::DragValue::new(end).speed(0.1));
+                    });
+                }
+                if expression.derivatives.is_some() {
+                    derivatives_ui(ui, expression, &variables);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            self.expressions.remove(index);
+            changed = true;
+        }
+        if ui
+            .button(add_text(TextType::Paragraph, tr("calculator.add")))
+            .clicked()
+        {
+            let color = PALETTE[self.expressions.len() % PALETTE.len()];
+            self.expressions.push(Expression::new("", color));
+        }
+        if changed {
+            self.update_sliders();
+        }
+    }
+
+    /// Puts the tangent of the visible function of `x` whose graph passes
+    /// closest above or below `[x, y]` at `x`.
+    fn add_tangent(&mut self, [x, y]: [f64; 2]) {
+        let variables: Vec<(&str, f64)> = self
+            .sliders
+            .iter()
+            .map(|(name, slider)| (name.as_str(), slider.value))
+            .collect();
+        let nearest = self
+            .expressions
+            .iter_mut()
+            .filter(|expression| expression.visible)
+            .filter_map(|expression| {
+                let Some(Graph::Function(f)) = &expression.graph else {
+                    return None;
+                };
+                let distance = (value_at(f, x, &variables) - y).abs();
+                distance.is_finite().then_some((distance, expression))
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b));
+        if let Some((_, expression)) = nearest {
+            expression.tangent_at = Some(x);
+        }
+    }
+
+    fn sliders_ui(&mut self, ui: &mut egui::Ui) {
+        let dt = f64::from(ui.input(|input| input.stable_dt).min(0.1));
+        for (name, slider) in &mut self.sliders {
+            if slider.playing {
+                slider.animate(dt);
+                ui.ctx().request_repaint();
+            }
+            ui.horizontal(|ui| {
+                let (icon, hint) = if slider.playing {
+                    ("⏸", "calculator.pause")
+                } else {
+                    ("▶", "calculator.play")
+                };
+                if ui.small_button(icon).on_hover_text(tr(hint)).clicked() {
+                    slider.playing = !slider.playing;
+                }
+                ui.label(add_text(TextType::Paragraph, &format!("{name} =")));
+                ui.add(egui::DragValue::new(&mut slider.min).speed(0.1))
+                    .on_hover_text(tr("calculator.slider_min"));
+                let range = slider.min..=slider.max;
+                ui.add(egui::Slider::new(&mut slider.value, range));
+                ui.add(egui::DragValue::new(&mut slider.max).speed(0.1))
+                    .on_hover_text(tr("calculator.slider_max"));
+            });
+        }
+    }
+}
+
+impl super::MiniApp for GraphingCalculator {
+    fn id(&self) -> &'static str {
+        "graphing_calculator"
+    }
+
+    fn title(&self) -> &'static str {
+        tr("window.graphing_calculator")
+    }
+
+    fn icon(&self) -> &'static str {
+        "📈"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        self.expressions_ui(ui);
+        if !self.sliders.is_empty() {
+            ui.separator();
+            self.sliders_ui(ui);
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.toggle_value(&mut self.tangent_mode, tr("calculator.tangent_mode"))
+                .on_hover_text(tr("calculator.tangent_mode_hint"));
+            ui.label(egui::RichText::new(tr("calculator.plot_hint")).weak());
+        });
+
+        let height = (ui.clip_rect().bottom() - ui.cursor().top()).max(MIN_PLOT_HEIGHT);
+        let size = egui::vec2(ui.available_width(), height);
+        let variables: Vec<(&str, f64)> = self
+            .sliders
+            .iter()
+            .map(|(name, slider)| (name.as_str(), slider.value))
+            .collect();
+        let response = Plot::new("graphing_calculator")
+            .height(height)
+            .legend(Legend::default())
+            .include_x(-10.0)
+            .include_x(10.0)
+            .include_y(-6.0)
+            .include_y(6.0)
+            .show(ui, |plot_ui| {
+                for expression in self.expressions.iter().filter(|e| e.visible) {
+                    if let Some(graph) = &expression.graph {
+                        draw::draw(plot_ui, expression, graph, &variables, size);
+                    }
+                }
+                let clicked = plot_ui.response().clicked();
+                (
+                    plot_ui.plot_bounds(),
+                    clicked.then(|| plot_ui.pointer_coordinate()).flatten(),
+                )
+            });
+        let (bounds, clicked) = response.inner;
+        // The plot only knows its new bounds after drawing; sample again for them.
+        if response.transform.bounds() != &bounds {
+            ui.ctx().request_repaint();
+        }
+        if let Some(point) = clicked.filter(|_| self.tangent_mode) {
+            self.add_tangent([point.x, point.y]);
+        }
+    }
+
+    fn save(&self, storage: &mut dyn eframe::Storage, key: &str) {
+        eframe::set_value(storage, key, self);
+    }
+
+    fn load(&mut self, storage: &dyn eframe::Storage, key: &str) {
+        if let Some(mut calculator) = eframe::get_value::<Self>(storage, key) {
+            for expression in &mut calculator.expressions {
+                expression.parse();
+            }
+            calculator.update_sliders();
+            *self = calculator;
+        }
+    }
+}
+
+/// Toggles for drawing the derivatives of a function of `x`, the simplified
+/// derivatives that are drawn, and the tangent if there is one.
+fn derivatives_ui(ui: &mut egui::Ui, expression: &mut Expression, variables: &[(&str, f64)]) {
+    ui.horizontal(|ui| {
+        let hints = ["calculator.derivative", "calculator.second_derivative"];
+        for ((shown, prime), hint) in expression
+            .show_derivatives
+            .iter_mut()
+            .zip(PRIMES)
+            .zip(hints)
+        {
+            ui.checkbox(shown, format!("y{prime}"))
+                .on_hover_text(tr(hint));
+        }
+    });
+    for order in 1..=2 {
+        if expression.show_derivatives[order - 1] {
+            if let Some(name) = expression.derivative_name(order) {
+                ui.label(add_text(TextType::Paragraph, &name));
+            }
+        }
+    }
+    let Some(x) = expression.tangent_at else {
+        return;
+    };
+    let text = match expression.tangent(variables) {
+        Some(([x, y], slope)) => {
+            // y = slope x + (y - slope x)
+            let line = Expr::Binary(
+                BinaryOp::Add,
+                Box::new(Expr::Binary(
+                    BinaryOp::Mul,
+                    Box::new(Expr::Number(round(slope))),
+                    Box::new(Expr::Variable("x".to_owned())),
+                )),
+                Box::new(Expr::Number(round(y - slope * x))),
+            );
+            tr_with(
+                "calculator.tangent",
+                &[
+                    ("x", &round(x).to_string()),
+                    ("equation", &format!("y = {}", line.simplified())),
+                ],
+            )
+        }
+        None => tr_with("calculator.no_tangent", &[("x", &round(x).to_string())]),
+    };
+    ui.horizontal(|ui| {
+        ui.label(add_text(TextType::Paragraph, &text));
+        if ui
+            .small_button("🗙")
+            .on_hover_text(tr("calculator.remove_tangent"))
+            .clicked()
+        {
+            expression.tangent_at = None;
+        }
+    });
+}
+
+/// The text with the part at fault underlined and highlighted.
+fn highlight_error(ui: &egui::Ui, text: &str, span: Option<Range<usize>>) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let normal = egui::TextFormat::simple(font_id, ui.visuals().text_color());
+    let mut job = egui::text::LayoutJob::default();
+    // The span is of the text as it was parsed, which may have been edited since.
+    let span = span.filter(|span| text.get(span.clone()).is_some());
+    let Some(span) = span else {
+        job.append(text, 0.0, normal);
+        return job;
+    };
+    let error_color = ui.visuals().error_fg_color;
+    let error = egui::TextFormat {
+        color: error_color,
+        background: error_color.gamma_multiply(0.2),
+        underline: egui::Stroke::new(1.5, error_color),
+        ..normal.clone()
+    };
+    job.append(&text[..span.start], 0.0, normal.clone());
+    job.append(&text[span.clone()], 0.0, error);
+    job.append(&text[span.end..], 0.0, normal);
+    job
+}
@@ -0,0 +1,819 @@
+//! Parsing what is typed into the graphing calculator.
+//!
+//! The usual precedence applies: `^` binds tightest and to the right, then
+//! unary minus, then multiplication and division, then addition and
+//! subtraction. A product can be written without `*`, as in `2x`, `3(x+1)`
+//! or `x sin x`, and a function's argument without parentheses, as in
+//! `sin 2x`, which is `sin(2x)`. `sin^2 x` is `(sin x)^2`. Numbers can be
+//! written in scientific notation, as in `1.5e3`; `2e` on its own is `2·e`.
+//!
+//! What kind of graph the text describes is told by its shape; see [`parse_graph`].
+
+use std::ops::Range;
+
+use super::expr::{BinaryOp, Constant, Expr, Function};
+use super::graph::{Graph, Relation};
+use crate::i18n::{tr, tr_with};
+
+/// How deeply parentheses, function calls, signs and exponents may nest,
+/// counting each term a sum or product chains on as one level more, as it
+/// is in the expression tree. This keeps parsing, evaluating and
+/// differentiating well within the stack.
+const MAX_DEPTH: usize = 64;
+
+/// Why the text is not an expression, and where in it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    /// Byte range of the text at fault.
+    pub span: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ErrorKind {
+    UnexpectedCharacter(char),
+    /// Digits with more than one decimal point, or a number too large for an `f64`.
+    InvalidNumber,
+    /// An operator or the end of the text where a number, variable or `(` belongs.
+    ExpectedOperand,
+    /// The text ends after an operator.
+    MissingOperand,
+    UnclosedParenthesis,
+    UnmatchedParenthesis,
+    UnexpectedToken,
+    MissingArgument(Function),
+    /// A name of several letters that is called like a function but is not one, like `sign x`.
+    UnknownFunction(String),
+    /// Deeper than `MAX_DEPTH`, by nesting or by a long chain of terms.
+    TooDeep,
+    /// A variable that has a meaning of its own, like `y` in a function of `x`.
+    ReservedVariable(String),
+}
+
+impl ParseError {
+    /// The message in the current locale, quoting the text at fault.
+    pub fn message(&self, source: &str) -> String {
+        let text = &source[self.span.clone()];
+        match &self.kind {
+            ErrorKind::UnexpectedCharacter(character) => tr_with(
+                "calculator.error.unexpected_character",
+                &[("text", &character.to_string())],
+            ),
+            ErrorKind::InvalidNumber => {
+                tr_with("calculator.error.invalid_number", &[("text", text)])
+            }
+            ErrorKind::ExpectedOperand => {
+                tr_with("calculator.error.expected_operand", &[("text", text)])
+            }
+            ErrorKind::MissingOperand => {
+                tr_with("calculator.error.missing_operand", &[("text", text)])
+            }
+            ErrorKind::UnclosedParenthesis => tr("calculator.error.unclosed").to_owned(),
+            ErrorKind::UnmatchedParenthesis => tr("calculator.error.unmatched").to_owned(),
+            ErrorKind::UnexpectedToken => {
+                tr_with("calculator.error.unexpected_token", &[("text", text)])
+            }
+            ErrorKind::MissingArgument(function) => tr_with(
+                "calculator.error.missing_argument",
+                &[("function", function.name())],
+            ),
+            ErrorKind::ReservedVariable(name) => {
+                tr_with("calculator.error.reserved_variable", &[("name", name)])
+            }
+            ErrorKind::UnknownFunction(name) => {
+                tr_with("calculator.error.unknown_function", &[("name", name)])
+            }
+            ErrorKind::TooDeep => tr("calculator.error.too_deep").to_owned(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Constant(Constant),
+    Function(Function),
+    Variable(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    Comma,
+    Relation(Relation),
+    LeftParen,
+    RightParen,
+}
+
+impl Token {
+    /// Whether the token can start an operand, and so follow another one in an implicit product.
+    fn starts_operand(&self) -> bool {
+        matches!(
+            self,
+            Self::Number(_)
+                | Self::Constant(_)
+                | Self::Function(_)
+                | Self::Variable(_)
+                | Self::LeftParen
+        )
+    }
+}
+
+/// Parses any of the graphs the calculator draws:
+///
+/// - `sin x` or `y = sin x`, a function of `x`;
+/// - `(cos t, sin t)`, a parametric curve in `t`;
+/// - `r = 1 + cos θ`, a polar curve in `θ`, which can be written `theta`;
+/// - `x^2 + y^2 = 1` or `y < sin x`, a relation between `x` and `y`.
+///
+/// Other variables are left free, to be given values later.
+pub fn parse_graph(source: &str) -> Result<Graph, ParseError> {
+    let tokens = tokenize(source)?;
+    let whole = 0..source.len();
+    let relations: Vec<usize> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, (token, _))| matches!(token, Token::Relation(_)))
+        .map(|(index, _)| index)
+        .collect();
+    let index = match relations.as_slice() {
+        [] => {
+            if let Some(comma) = parametric_comma(&tokens) {
+                let (open, comma_span) = (tokens[0].1.clone(), tokens[comma].1.clone());
+                let x = parse_expr(&tokens[1..comma], open, &["x", "y"])?;
+                let y = parse_expr(
+                    &tokens[comma + 1..tokens.len() - 1],
+                    comma_span,
+                    &["x", "y"],
+                )?;
+                return Ok(Graph::Parametric(x, y));
+            }
+            return Ok(Graph::Function(parse_expr(&tokens, whole, &["y"])?));
+        }
+        [index] => *index,
+        [_, second, ..] => {
+            return Err(ParseError {
+                kind: ErrorKind::UnexpectedToken,
+                span: tokens[*second].1.clone(),
+            })
+        }
+    };
+
+    let (left, right) = (&tokens[..index], &tokens[index + 1..]);
+    let (Token::Relation(relation), span) = tokens[index].clone() else {
+        unreachable!("relations only holds indices of relations");
+    };
+    if left.is_empty() {
+        return Err(ParseError {
+            kind: ErrorKind::ExpectedOperand,
+            span,
+        });
+    }
+    let mentions = |tokens: &[(Token, Range<usize>)], name: &str| {
+        tokens
+            .iter()
+            .any(|(token, _)| matches!(token, Token::Variable(variable) if variable == name))
+    };
+    match (left, relation) {
+        ([(Token::Variable(name), _)], Relation::Equal) if name == "y" && !mentions(right, "y") => {
+            Ok(Graph::Function(parse_expr(right, span, &["y"])?))
+        }
+        ([(Token::Variable(name), _)], Relation::Equal) if name == "r" => {
+            Ok(Graph::Polar(parse_expr(right, span, &["x", "y", "r"])?))
+        }
+        _ => Ok(Graph::Relation(
+            parse_expr(left, span.clone(), &[])?,
+            relation,
+            parse_expr(right, span, &[])?,
+        )),
+    }
+}
+
+/// The index of the comma in tokens of the form `(…, …)`.
+fn parametric_comma(tokens: &[(Token, Range<usize>)]) -> Option<usize> {
+    if !matches!(tokens.first(), Some((Token::LeftParen, _)))
+        || !matches!(tokens.last(), Some((Token::RightParen, _)))
+    {
+        return None;
+    }
+    let mut depth = 0;
+    let mut comma = None;
+    for (index, (token, _)) in tokens.iter().enumerate() {
+        match token {
+            Token::LeftParen => depth += 1,
+            Token::RightParen => {
+                depth -= 1;
+                // The first parenthesis has to close at the end.
+                if depth == 0 && index + 1 < tokens.len() {
+                    return None;
+                }
+            }
+            Token::Comma if depth == 1 && comma.is_none() => comma = Some(index),
+            _ => {}
+        }
+    }
+    comma
+}
+
+/// Parses the tokens as one expression, pointing at `before` if there are none.
+fn parse_expr(
+    tokens: &[(Token, Range<usize>)],
+    before: Range<usize>,
+    reserved: &[&str],
+) -> Result<Expr, ParseError> {
+    Parser {
+        tokens: tokens.to_vec(),
+        position: 0,
+        depth: 0,
+        before,
+        reserved,
+    }
+    .parse()
+}
+
+/// Splits the text into tokens with their byte ranges.
+///
+/// A run of letters is read as the longest function or constant name at its
+/// start, or else as a one-letter variable, so `xy` is `x y` and `pix` is
+/// `pi x`. A variable can have a subscript, as in `a_1`.
+fn tokenize(source: &str) -> Result<Vec<(Token, Range<usize>)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(start, character)) = chars.peek() {
+        let token = match character {
+            _ if character.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '0'..='9' | '.' => {
+                let mut end = start;
+                while let Some(&(index, character)) = chars.peek() {
+                    if !(character.is_ascii_digit() || character == '.') {
+                        break;
+                    }
+                    end = index + 1;
+                    chars.next();
+                }
+                // An exponent only if digits follow, so that `2e` stays `2·e`.
+                let exponent = exponent_len(&source[end..]);
+                if exponent > 0 {
+                    end += exponent;
+                    while chars.peek().map_or(false, |&(index, _)| index < end) {
+                        chars.next();
+                    }
+                }
+                let value = source[start..end].parse::<f64>().ok();
+                let Some(value) = value.filter(|value| value.is_finite()) else {
+                    return Err(ParseError {
+                        kind: ErrorKind::InvalidNumber,
+                        span: start..end,
+                    });
+                };
+                tokens.push((Token::Number(value), start..end));
+                continue;
+            }
+            _ if character.is_alphabetic() || character == '√' => {
+                if let Some(len) = unknown_function_len(&source[start..]) {
+                    return Err(ParseError {
+                        kind: ErrorKind::UnknownFunction(source[start..start + len].to_owned()),
+                        span: start..start + len,
+                    });
+                }
+                let (token, len) = name_at(&source[start..]);
+                let mut end = start + len;
+                let token = match token {
+                    Token::Variable(mut name) if source[end..].starts_with('_') => {
+                        let subscript = source[end + 1..]
+                            .find(|character: char| !character.is_alphanumeric())
+                            .unwrap_or(source.len() - end - 1);
+                        if subscript > 0 {
+                            name.push_str(&source[end..end + 1 + subscript]);
+                            end += 1 + subscript;
+                        }
+                        Token::Variable(name)
+                    }
+                    token => token,
+                };
+                while chars.peek().map_or(false, |&(index, _)| index < end) {
+                    chars.next();
+                }
+                tokens.push((token, start..end));
+                continue;
+            }
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' | '·' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '^' => Token::Caret,
+            ',' => Token::Comma,
+            '=' => Token::Relation(Relation::Equal),
+            '≤' => Token::Relation(Relation::LessEqual),
+            '≥' => Token::Relation(Relation::GreaterEqual),
+            '<' | '>' => {
+                let or_equal = source[start + 1..].starts_with('=');
+                let relation = match (character, or_equal) {
+                    ('<', false) => Relation::Less,
+                    ('<', true) => Relation::LessEqual,
+                    (_, false) => Relation::Greater,
+                    (_, true) => Relation::GreaterEqual,
+                };
+                chars.next();
+                let mut end = start + 1;
+                if or_equal {
+                    chars.next();
+                    end += 1;
+                }
+                tokens.push((Token::Relation(relation), start..end));
+                continue;
+            }
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            _ => {
+                return Err(ParseError {
+                    kind: ErrorKind::UnexpectedCharacter(character),
+                    span: start..start + character.len_utf8(),
+                })
+            }
+        };
+        chars.next();
+        tokens.push((token, start..start + character.len_utf8()));
+    }
+    Ok(tokens)
+}
+
+/// The length in bytes of the exponent of a number in scientific notation
+/// at the start of `text`, like `e-3`, or 0 if there is none.
+fn exponent_len(text: &str) -> usize {
+    let Some(rest) = text.strip_prefix(['e', 'E']) else {
+        return 0;
+    };
+    let sign = usize::from(rest.starts_with(['+', '-']));
+    let digits = rest[sign..]
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(rest.len() - sign);
+    if digits == 0 {
+        0
+    } else {
+        1 + sign + digits
+    }
+}
+
+/// The length in bytes of the letters at the start of `text` if they are a
+/// name that is not a function, a constant or `theta`, yet is followed by
+/// an argument as if it were a function, like `sign` in `sign x` or `sign(x)`.
+/// Letters that are only single-letter variables are otherwise an implicit
+/// product, as in `ab`.
+fn unknown_function_len(text: &str) -> Option<usize> {
+    let len = text
+        .find(|character: char| !character.is_alphabetic())
+        .unwrap_or(text.len());
+    let letters = &text[..len];
+    if letters.chars().count() < 2 {
+        return None;
+    }
+    let mut rest = letters;
+    while !rest.is_empty() {
+        match name_at(rest) {
+            (Token::Variable(name), name_len) if name.chars().count() == 1 && name != "θ" => {
+                rest = &rest[name_len..];
+            }
+            _ => return None,
+        }
+    }
+    let next = text[len..].trim_start().chars().next()?;
+    let takes_argument = next == '(' || next == '√' || next == '.' || next.is_alphanumeric();
+    takes_argument.then_some(len)
+}
+
+/// The name at the start of `text`, and its length in bytes.
+fn name_at(text: &str) -> (Token, usize) {
+    if text.starts_with("theta") {
+        return (Token::Variable("θ".to_owned()), "theta".len());
+    }
+    let functions = Function::ALL.into_iter().flat_map(|function| {
+        function
+            .names()
+            .iter()
+            .map(move |name| (Token::Function(function), *name))
+    });
+    let constants = Constant::ALL.into_iter().flat_map(|constant| {
+        constant
+            .names()
+            .iter()
+            .map(move |name| (Token::Constant(constant), *name))
+    });
+    functions
+        .chain(constants)
+        .filter(|(_, name)| text.starts_with(name))
+        .max_by_key(|(_, name)| name.len())
+        .map(|(token, name)| (token, name.len()))
+        .unwrap_or_else(|| {
+            let letter = text.chars().next().unwrap_or_default();
+            (Token::Variable(letter.to_string()), letter.len_utf8())
+        })
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token, Range<usize>)>,
+    position: usize,
+    /// How deeply nested the current position is.
+    depth: usize,
+    /// The text just before the tokens, blamed when they end too early.
+    before: Range<usize>,
+    /// Variables the expression may not use.
+    reserved: &'a [&'a str],
+}
+
+impl Parser<'_> {
+    fn parse(mut self) -> Result<Expr, ParseError> {
+        let expr = self.sum()?;
+        match self.tokens.get(self.position) {
+            None => Ok(expr),
+            Some((Token::RightParen, span)) => Err(ParseError {
+                kind: ErrorKind::UnmatchedParenthesis,
+                span: span.clone(),
+            }),
+            Some((_, span)) => Err(ParseError {
+                kind: ErrorKind::UnexpectedToken,
+                span: span.clone(),
+            }),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    /// Moves past the next token if it is `token`.
+    fn eat(&mut self, token: &Token) -> bool {
+        let matches = self.peek() == Some(token);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    /// Parses with `parse` one level deeper, unless that is too deep.
+    fn nested(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<Expr, ParseError>,
+    ) -> Result<Expr, ParseError> {
+        self.deepen()?;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Goes one level deeper, unless that is too deep.
+    fn deepen(&mut self) -> Result<(), ParseError> {
+        if self.depth == MAX_DEPTH {
+            let span = self
+                .tokens
+                .get(self.position)
+                .map_or(self.before.clone(), |(_, span)| span.clone());
+            return Err(ParseError {
+                kind: ErrorKind::TooDeep,
+                span,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// The error for a missing operand at the current position.
+    fn expected_operand(&self) -> ParseError {
+        match self.tokens.get(self.position) {
+            Some((_, span)) => ParseError {
+                kind: ErrorKind::ExpectedOperand,
+                span: span.clone(),
+            },
+            None => ParseError {
+                kind: ErrorKind::MissingOperand,
+                span: self
+                    .tokens
+                    .last()
+                    .map_or(self.before.clone(), |(_, span)| span.clone()),
+            },
+        }
+    }
+
+    /// `product (("+" | "-") product)*`
+    fn sum(&mut self) -> Result<Expr, ParseError> {
+        let depth = self.depth;
+        let mut expr = self.product()?;
+        loop {
+            let op = if self.eat(&Token::Plus) {
+                BinaryOp::Add
+            } else if self.eat(&Token::Minus) {
+                BinaryOp::Sub
+            } else {
+                self.depth = depth;
+                return Ok(expr);
+            };
+            // Every term nests the sum so far one level deeper.
+            self.deepen()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.product()?));
+        }
+    }
+
+    /// `signed (("*" | "/") signed | power)*`, the second alternative being an implicit product.
+    fn product(&mut self) -> Result<Expr, ParseError> {
+        let depth = self.depth;
+        let mut expr = self.signed()?;
+        loop {
+            if self.peek().map_or(false, |token| {
+                matches!(token, Token::Star | Token::Slash) || token.starts_operand()
+            }) {
+                self.deepen()?;
+            }
+            let (op, right) = if self.eat(&Token::Star) {
+                (BinaryOp::Mul, self.signed()?)
+            } else if self.eat(&Token::Slash) {
+                (BinaryOp::Div, self.signed()?)
+            } else if self.peek().map_or(false, Token::starts_operand) {
+                (BinaryOp::Mul, self.power()?)
+            } else {
+                self.depth = depth;
+                return Ok(expr);
+            };
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    /// `("-" | "+")* power`
+    fn signed(&mut self) -> Result<Expr, ParseError> {
+        if self.eat(&Token::Minus) {
+            Ok(Expr::Neg(Box::new(self.nested(Self::signed)?)))
+        } else if self.eat(&Token::Plus) {
+            self.nested(Self::signed)
+        } else {
+            self.power()
+        }
+    }
+
+    /// `operand ("^" signed)?`
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let base = self.operand()?;
+        if self.eat(&Token::Caret) {
+            let exponent = self.nested(Self::signed)?;
+            Ok(Expr::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(exponent),
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+
+    fn operand(&mut self) -> Result<Expr, ParseError> {
+        let Some((token, span)) = self.tokens.get(self.position).cloned() else {
+            return Err(self.expected_operand());
+        };
+        let expr = match token {
+            Token::Number(value) => Expr::Number(value),
+            Token::Constant(constant) => Expr::Constant(constant),
+            Token::Variable(name) => {
+                if self.reserved.contains(&name.as_str()) {
+                    return Err(ParseError {
+                        kind: ErrorKind::ReservedVariable(name),
+                        span,
+                    });
+                }
+                Expr::Variable(name)
+            }
+            Token::LeftParen => {
+                self.position += 1;
+                let expr = self.nested(Self::sum)?;
+                return self.close_parenthesis(expr, span);
+            }
+            Token::Function(function) => {
+                self.position += 1;
+                return self.nested(|parser| parser.call(function, span));
+            }
+            _ => return Err(self.expected_operand()),
+        };
+        self.position += 1;
+        Ok(expr)
+    }
+
+    /// Expects the `)` matching the `(` at `open`.
+    fn close_parenthesis(&mut self, expr: Expr, open: Range<usize>) -> Result<Expr, ParseError> {
+        match self.tokens.get(self.position) {
+            Some((Token::RightParen, _)) => {
+                self.position += 1;
+                Ok(expr)
+            }
+            Some((_, span)) => Err(ParseError {
+                kind: ErrorKind::UnexpectedToken,
+                span: span.clone(),
+            }),
+            None => Err(ParseError {
+                kind: ErrorKind::UnclosedParenthesis,
+                span: open,
+            }),
+        }
+    }
+
+    /// The argument of the function whose name was at `name`: `("^" signed)? ("(" sum ")" | power+)`.
+    fn call(&mut self, function: Function, name: Range<usize>) -> Result<Expr, ParseError> {
+        let exponent = if self.eat(&Token::Caret) {
+            Some(self.signed()?)
+        } else {
+            None
+        };
+        let missing_argument = ParseError {
+            kind: ErrorKind::MissingArgument(function),
+            span: name,
+        };
+        let argument = match self.tokens.get(self.position).cloned() {
+            Some((Token::LeftParen, open)) => {
+                self.position += 1;
+                let argument = self.sum()?;
+                self.close_parenthesis(argument, open)?
+            }
+            Some((token, _)) if token.starts_operand() => {
+                // `sin 2x` is `sin(2x)`, but `sin x cos x` is `sin(x) cos(x)`.
+                let depth = self.depth;
+                let mut argument = self.power()?;
+                while self.peek().map_or(false, |token| {
+                    token.starts_operand() && !matches!(token, Token::Function(_))
+                }) {
+                    self.deepen()?;
+                    argument =
+                        Expr::Binary(BinaryOp::Mul, Box::new(argument), Box::new(self.power()?));
+                }
+                self.depth = depth;
+                argument
+            }
+            _ => return Err(missing_argument),
+        };
+        let call = Expr::Call(function, Box::new(argument));
+        Ok(match exponent {
+            Some(exponent) => Expr::Binary(BinaryOp::Pow, Box::new(call), Box::new(exponent)),
+            None => call,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The expression with every operation parenthesized, to check how it was grouped.
+    fn grouped(expr: &Expr) -> String {
+        match expr {
+            Expr::Number(value) => value.to_string(),
+            Expr::Constant(constant) => constant.names()[0].to_owned(),
+            Expr::Variable(name) => name.clone(),
+            Expr::Neg(operand) => format!("(-{})", grouped(operand)),
+            Expr::Call(function, argument) => format!("{}({})", function.name(), grouped(argument)),
+            Expr::Binary(op, left, right) => {
+                let op = match op {
+                    BinaryOp::Add => "+",
+                    BinaryOp::Sub => "-",
+                    BinaryOp::Mul => "*",
+                    BinaryOp::Div => "/",
+                    BinaryOp::Pow => "^",
+                };
+                format!("({} {op} {})", grouped(left), grouped(right))
+            }
+        }
+    }
+
+    fn function(source: &str) -> String {
+        match parse_graph(source) {
+            Ok(Graph::Function(expr)) => grouped(&expr),
+            other => panic!("{source:?} is not a function: {other:?}"),
+        }
+    }
+
+    fn error(source: &str) -> (ErrorKind, &str) {
+        let error = parse_graph(source).expect_err(source);
+        (error.kind, &source[error.span])
+    }
+
+    #[test]
+    fn applies_precedence() {
+        assert_eq!(function("1 + 2 * 3"), "(1 + (2 * 3))");
+        assert_eq!(function("1 - 2 - 3"), "((1 - 2) - 3)");
+        assert_eq!(function("8 / 4 / 2"), "((8 / 4) / 2)");
+        assert_eq!(function("2^3^2"), "(2 ^ (3 ^ 2))");
+        assert_eq!(function("-x^2"), "(-(x ^ 2))");
+        assert_eq!(function("2^-x"), "(2 ^ (-x))");
+        assert_eq!(function("(1 + x)^2"), "((1 + x) ^ 2)");
+    }
+
+    #[test]
+    fn reads_implicit_products_and_arguments() {
+        assert_eq!(function("2x"), "(2 * x)");
+        assert_eq!(function("3(x + 1)"), "(3 * (x + 1))");
+        assert_eq!(function("1/2x"), "((1 / 2) * x)");
+        assert_eq!(function("2x^2"), "(2 * (x ^ 2))");
+        assert_eq!(function("sin 2x"), "sin((2 * x))");
+        assert_eq!(function("sin x cos x"), "(sin(x) * cos(x))");
+        assert_eq!(function("sin^2 x"), "(sin(x) ^ 2)");
+        assert_eq!(function("pix"), "(π * x)");
+        assert_eq!(function("√x"), "sqrt(x)");
+        assert_eq!(function("a_1 x"), "(a_1 * x)");
+    }
+
+    #[test]
+    fn reads_scientific_notation() {
+        assert_eq!(function("1.5e3"), "1500");
+        assert_eq!(function("2E-3"), "0.002");
+        assert_eq!(function("1e+2x"), "(100 * x)");
+        assert_eq!(function("2e"), "(2 * e)");
+        assert_eq!(function("2e-x"), "((2 * e) - x)");
+    }
+
+    #[test]
+    fn tells_the_kind_of_graph() {
+        assert!(matches!(parse_graph("y = 2x"), Ok(Graph::Function(_))));
+        assert!(matches!(
+            parse_graph("(cos t, sin t)"),
+            Ok(Graph::Parametric(..))
+        ));
+        assert!(matches!(
+            parse_graph("r = 1 + cos theta"),
+            Ok(Graph::Polar(_))
+        ));
+        assert!(matches!(
+            parse_graph("x^2 + y^2 = 1"),
+            Ok(Graph::Relation(_, Relation::Equal, _))
+        ));
+        assert!(matches!(
+            parse_graph("y <= sin x"),
+            Ok(Graph::Relation(_, Relation::LessEqual, _))
+        ));
+        // Not a parametric curve, as the first parenthesis closes before the end.
+        assert!(matches!(parse_graph("(x, 1) + 1"), Err(_)));
+    }
+
+    #[test]
+    fn points_at_the_error() {
+        assert_eq!(error("2 + $"), (ErrorKind::UnexpectedCharacter('$'), "$"));
+        assert_eq!(error("1.2.3 + x"), (ErrorKind::InvalidNumber, "1.2.3"));
+        assert_eq!(error("1e999"), (ErrorKind::InvalidNumber, "1e999"));
+        assert_eq!(error("2 * / x"), (ErrorKind::ExpectedOperand, "/"));
+        assert_eq!(error("2 +"), (ErrorKind::MissingOperand, "+"));
+        assert_eq!(error("(x + 1"), (ErrorKind::UnclosedParenthesis, "("));
+        assert_eq!(error("x + 1)"), (ErrorKind::UnmatchedParenthesis, ")"));
+        assert_eq!(
+            error("sin + 1"),
+            (ErrorKind::MissingArgument(Function::Sin), "sin")
+        );
+        assert_eq!(error("x = y = 1"), (ErrorKind::UnexpectedToken, "="));
+        assert_eq!(error("= 1"), (ErrorKind::ExpectedOperand, "="));
+        assert_eq!(
+            error("2 + y"),
+            (ErrorKind::ReservedVariable("y".to_owned()), "y")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_functions() {
+        assert_eq!(
+            error("1 + sign x"),
+            (ErrorKind::UnknownFunction("sign".to_owned()), "sign")
+        );
+        assert_eq!(
+            error("foo(x)"),
+            (ErrorKind::UnknownFunction("foo".to_owned()), "foo")
+        );
+        // Single letters side by side are still a product.
+        assert_eq!(function("ab"), "(a * b)");
+        assert_eq!(function("ab + 1"), "((a * b) + 1)");
+    }
+
+    #[test]
+    fn caps_the_nesting_depth() {
+        let nested = |depth: usize| format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse_graph(&nested(MAX_DEPTH - 1)).is_ok());
+        assert_eq!(error(&nested(MAX_DEPTH + 1)).0, ErrorKind::TooDeep);
+        assert_eq!(error(&"-".repeat(10_000)).0, ErrorKind::TooDeep);
+        assert_eq!(error(&"x^".repeat(10_000)).0, ErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn caps_the_length_of_chains() {
+        let chain = |operator: &str, terms: usize| vec!["x"; terms].join(operator);
+        for operator in ["+", "-", "*", "/", " "] {
+            let Ok(Graph::Function(longest)) = parse_graph(&chain(operator, MAX_DEPTH)) else {
+                panic!("{operator:?} chains of {MAX_DEPTH} terms should parse");
+            };
+            // What the calculator does with every function it is given.
+            longest.derivative("x").simplified();
+            let source = chain(operator, 3000);
+            assert_eq!(error(&source).0, ErrorKind::TooDeep, "{operator:?}");
+        }
+        assert_eq!(
+            error(&format!("sin {}", chain(" ", 3000))).0,
+            ErrorKind::TooDeep
+        );
+        // Terms inside parentheses count towards the depth around them.
+        let nested = format!("{}{}{}", "(".repeat(40), chain("+", 40), ")".repeat(40));
+        assert_eq!(error(&nested).0, ErrorKind::TooDeep);
+        assert!(parse_graph(&format!("{}{}", chain("+", 40), "+ (x + x)".repeat(20))).is_ok());
+    }
+}
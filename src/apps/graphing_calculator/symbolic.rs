@@ -0,0 +1,630 @@
+//! Symbolic differentiation and simplification of expressions.
+
+use super::expr::{BinaryOp, Constant, Expr, Function};
+
+impl Expr {
+    /// The derivative with respect to `variable`, other variables being constants.
+    /// It is not simplified.
+    pub fn derivative(&self, variable: &str) -> Expr {
+        match self {
+            Self::Number(_) | Self::Constant(_) => number(0.0),
+            Self::Variable(name) => number(if name == variable { 1.0 } else { 0.0 }),
+            Self::Neg(operand) => neg(operand.derivative(variable)),
+            Self::Binary(op, left, right) => {
+                let (left, right) = (left.as_ref(), right.as_ref());
+                let (dl, dr) = (left.derivative(variable), right.derivative(variable));
+                match op {
+                    BinaryOp::Add => add(dl, dr),
+                    BinaryOp::Sub => sub(dl, dr),
+                    BinaryOp::Mul => add(mul(dl, right.clone()), mul(left.clone(), dr)),
+                    BinaryOp::Div => div(
+                        sub(mul(dl, right.clone()), mul(left.clone(), dr)),
+                        pow(right.clone(), number(2.0)),
+                    ),
+                    BinaryOp::Pow if !right.depends_on(variable) => mul(
+                        mul(
+                            right.clone(),
+                            pow(left.clone(), sub(right.clone(), number(1.0))),
+                        ),
+                        dl,
+                    ),
+                    // Powers of zero are zero wherever they have a derivative, and ln(0) is not a number.
+                    BinaryOp::Pow if left.simplified() == number(0.0) => number(0.0),
+                    BinaryOp::Pow if !left.depends_on(variable) => {
+                        mul(mul(self.clone(), call(Function::Ln, left.clone())), dr)
+                    }
+                    // d(u^v) = u^v (v' ln u + v u' / u)
+                    BinaryOp::Pow => mul(
+                        self.clone(),
+                        add(
+                            mul(dr, call(Function::Ln, left.clone())),
+                            div(mul(right.clone(), dl), left.clone()),
+                        ),
+                    ),
+                }
+            }
+            Self::Call(function, argument) => {
+                let u = argument.as_ref().clone();
+                let outer = match function {
+                    Function::Sin => call(Function::Cos, u),
+                    Function::Cos => neg(call(Function::Sin, u)),
+                    Function::Tan => pow(call(Function::Sec, u), number(2.0)),
+                    Function::Sec => mul(call(Function::Sec, u.clone()), call(Function::Tan, u)),
+                    Function::Csc => {
+                        neg(mul(call(Function::Csc, u.clone()), call(Function::Cot, u)))
+                    }
+                    Function::Cot => neg(pow(call(Function::Csc, u), number(2.0))),
+                    Function::Asin => div(
+                        number(1.0),
+                        call(Function::Sqrt, sub(number(1.0), pow(u, number(2.0)))),
+                    ),
+                    Function::Acos => neg(div(
+                        number(1.0),
+                        call(Function::Sqrt, sub(number(1.0), pow(u, number(2.0)))),
+                    )),
+                    Function::Atan => div(number(1.0), add(number(1.0), pow(u, number(2.0)))),
+                    Function::Sinh => call(Function::Cosh, u),
+                    Function::Cosh => call(Function::Sinh, u),
+                    Function::Tanh => sub(number(1.0), pow(call(Function::Tanh, u), number(2.0))),
+                    Function::Sqrt => div(number(1.0), mul(number(2.0), call(Function::Sqrt, u))),
+                    Function::Exp => call(Function::Exp, u),
+                    Function::Ln => div(number(1.0), u),
+                    Function::Log => div(number(1.0), mul(u, call(Function::Ln, number(10.0)))),
+                    Function::Abs => div(u.clone(), call(Function::Abs, u)),
+                    // Zero wherever they are differentiable.
+                    Function::Floor | Function::Ceil => return number(0.0),
+                };
+                mul(outer, argument.derivative(variable))
+            }
+        }
+    }
+
+    /// Whether the value depends on `variable`.
+    pub fn depends_on(&self, variable: &str) -> bool {
+        match self {
+            Self::Number(_) | Self::Constant(_) => false,
+            Self::Variable(name) => name == variable,
+            Self::Neg(operand) | Self::Call(_, operand) => operand.depends_on(variable),
+            Self::Binary(_, left, right) => left.depends_on(variable) || right.depends_on(variable),
+        }
+    }
+
+    /// An equal expression with like terms and like factors gathered and
+    /// numbers folded, such as `0 x + 1 x^1 + x` to `2x`. Identities like
+    /// `x / x = 1` are applied without regard to where `x` is zero, but not
+    /// ones that would define it where it was undefined over whole
+    /// intervals: `sqrt(x)^2` is not `x` for negative `x`.
+    pub fn simplified(&self) -> Expr {
+        match self {
+            Self::Number(_) | Self::Constant(_) | Self::Variable(_) => self.clone(),
+            Self::Neg(_) | Self::Binary(BinaryOp::Add | BinaryOp::Sub, _, _) => {
+                let mut terms = Vec::new();
+                collect_terms(self, false, &mut terms);
+                terms.retain(|term| !term.coefficient.is_zero());
+                // Numbers last, as in `x^2 - 1`, but not at the cost of a leading
+                // minus, as in `1 - x^2`.
+                let (numbers, mut terms): (Vec<_>, Vec<_>) =
+                    terms.into_iter().partition(|term| term.factors.is_empty());
+                terms.extend(numbers);
+                if let Some(first) = terms
+                    .iter()
+                    .position(|term| !term.coefficient.is_negative())
+                {
+                    let term = terms.remove(first);
+                    terms.insert(0, term);
+                }
+                let mut sum: Option<Expr> = None;
+                for term in terms {
+                    sum = Some(match sum {
+                        None => term.to_expr(),
+                        Some(sum) if term.coefficient.is_negative() => {
+                            sub(sum, term.negated().to_expr())
+                        }
+                        Some(sum) => add(sum, term.to_expr()),
+                    });
+                }
+                sum.unwrap_or(number(0.0))
+            }
+            Self::Binary(BinaryOp::Mul | BinaryOp::Div | BinaryOp::Pow, _, _) => {
+                Product::of(self).to_expr()
+            }
+            Self::Call(function, argument) => {
+                let argument = argument.simplified();
+                match (function, &argument) {
+                    (Function::Ln, Self::Constant(Constant::E)) => return number(1.0),
+                    // Only exact results, like cos(0) = 1 or sqrt(4) = 2.
+                    (_, Self::Number(value)) => {
+                        let result = function.apply(*value);
+                        if result.is_finite() && result.fract() == 0.0 {
+                            return number(result);
+                        }
+                    }
+                    _ => {}
+                }
+                call(*function, argument)
+            }
+        }
+    }
+}
+
+/// Adds the terms of the sum `expr`, negated or not, to `terms`, adding
+/// together those that differ only in their coefficient.
+fn collect_terms(expr: &Expr, negated: bool, terms: &mut Vec<Product>) {
+    match expr {
+        Expr::Binary(BinaryOp::Add, left, right) => {
+            collect_terms(left, negated, terms);
+            collect_terms(right, negated, terms);
+        }
+        Expr::Binary(BinaryOp::Sub, left, right) => {
+            collect_terms(left, negated, terms);
+            collect_terms(right, !negated, terms);
+        }
+        Expr::Neg(operand) => collect_terms(operand, !negated, terms),
+        _ => {
+            let simplified = expr.simplified();
+            // A product can come out as a sum, like `1 (x + 1)`.
+            if matches!(
+                simplified,
+                Expr::Binary(BinaryOp::Add | BinaryOp::Sub, _, _) | Expr::Neg(_)
+            ) {
+                return collect_terms(&simplified, negated, terms);
+            }
+            let mut term = Product::of(&simplified);
+            if negated {
+                term = term.negated();
+            }
+            match terms.iter_mut().find(|other| other.has_factors_of(&term)) {
+                Some(other) => other.coefficient = other.coefficient.add(term.coefficient),
+                None => terms.push(term),
+            }
+        }
+    }
+}
+
+/// A rational number, kept as a fraction of integers while it is one, so
+/// that `x^(1/3)` is not printed as `x^0.3333333333333333`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Ratio {
+    numerator: f64,
+    /// Positive, and 1 unless both are integers.
+    denominator: f64,
+}
+
+impl Ratio {
+    const ONE: Self = Self {
+        numerator: 1.0,
+        denominator: 1.0,
+    };
+
+    fn new(numerator: f64, denominator: f64) -> Self {
+        let is_integer = |value: f64| value.fract() == 0.0 && value.abs() < MAX_EXACT;
+        if denominator != 0.0 && is_integer(numerator) && is_integer(denominator) {
+            let divisor = gcd(numerator.abs(), denominator.abs()) * denominator.signum();
+            Self {
+                numerator: numerator / divisor,
+                denominator: denominator / divisor,
+            }
+        } else {
+            Self {
+                numerator: numerator / denominator,
+                denominator: 1.0,
+            }
+        }
+    }
+
+    /// The number `expr` is, if it is `n`, `-n` or `n / m` for numbers `n` and `m`.
+    fn of(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Number(value) => Some(Self::new(*value, 1.0)),
+            Expr::Neg(operand) => Self::of(operand).map(|ratio| ratio.mul(Self::new(-1.0, 1.0))),
+            Expr::Binary(BinaryOp::Div, numerator, denominator) => {
+                match (numerator.as_ref(), denominator.as_ref()) {
+                    (Expr::Number(n), Expr::Number(d)) if *d != 0.0 => Some(Self::new(*n, *d)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn is_zero(self) -> bool {
+        self.numerator == 0.0
+    }
+
+    fn is_negative(self) -> bool {
+        self.numerator < 0.0
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.denominator + other.numerator * self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+
+    fn powi(self, exponent: i32) -> Self {
+        if exponent < 0 {
+            Self::new(
+                self.denominator.powi(-exponent),
+                self.numerator.powi(-exponent),
+            )
+        } else {
+            Self::new(
+                self.numerator.powi(exponent),
+                self.denominator.powi(exponent),
+            )
+        }
+    }
+
+    fn to_expr(self) -> Expr {
+        if self.denominator == 1.0 {
+            number(self.numerator)
+        } else if self.is_negative() {
+            neg(div(number(-self.numerator), number(self.denominator)))
+        } else {
+            div(number(self.numerator), number(self.denominator))
+        }
+    }
+}
+
+/// Beyond this, integers in an `f64` are no longer exact.
+const MAX_EXACT: f64 = 9_007_199_254_740_992.0;
+
+fn gcd(mut a: f64, mut b: f64) -> f64 {
+    while b != 0.0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn is_integer(expr: &Expr) -> bool {
+    Ratio::of(expr).map_or(false, |ratio| {
+        ratio.denominator == 1.0 && ratio.numerator.fract() == 0.0
+    })
+}
+
+/// Whether it is never negative, as far as can be told without knowing the variables.
+fn is_non_negative(expr: &Expr) -> bool {
+    match expr {
+        Expr::Number(value) => *value >= 0.0,
+        Expr::Constant(_) => true,
+        Expr::Call(Function::Exp | Function::Abs | Function::Sqrt | Function::Cosh, _) => true,
+        Expr::Binary(BinaryOp::Pow, base, exponent) => {
+            let even = Ratio::of(exponent).map_or(false, |ratio| {
+                ratio.denominator == 1.0 && ratio.numerator % 2.0 == 0.0
+            });
+            even || is_non_negative(base)
+        }
+        Expr::Binary(BinaryOp::Add | BinaryOp::Mul | BinaryOp::Div, left, right) => {
+            is_non_negative(left) && is_non_negative(right)
+        }
+        _ => false,
+    }
+}
+
+/// Whether it is defined for all values of its variables but isolated
+/// points, so that cancelling it, as in `x / x = 1`, only gives it a value
+/// at points where it had none.
+fn is_defined_nearly_everywhere(expr: &Expr) -> bool {
+    match expr {
+        Expr::Number(_) | Expr::Constant(_) | Expr::Variable(_) => true,
+        Expr::Neg(operand) => is_defined_nearly_everywhere(operand),
+        Expr::Binary(BinaryOp::Pow, base, exponent) => {
+            let integer_power = is_integer(exponent) && is_defined_nearly_everywhere(base);
+            let positive_base = match base.as_ref() {
+                Expr::Number(value) => *value > 0.0,
+                Expr::Constant(_) => true,
+                _ => false,
+            };
+            integer_power || positive_base && is_defined_nearly_everywhere(exponent)
+        }
+        Expr::Binary(_, left, right) => {
+            is_defined_nearly_everywhere(left) && is_defined_nearly_everywhere(right)
+        }
+        Expr::Call(function, argument) => {
+            !matches!(
+                function,
+                Function::Sqrt | Function::Ln | Function::Log | Function::Asin | Function::Acos
+            ) && is_defined_nearly_everywhere(argument)
+        }
+    }
+}
+
+/// A product as a number times powers of distinct bases, like `2 x^2 sin(x)^-1`.
+struct Product {
+    coefficient: Ratio,
+    /// Bases with their exponents, both simplified, in the order they appear.
+    factors: Vec<(Expr, Expr)>,
+}
+
+impl Product {
+    fn of(expr: &Expr) -> Self {
+        let mut product = Self {
+            coefficient: Ratio::ONE,
+            factors: Vec::new(),
+        };
+        product.multiply(expr, &number(1.0));
+        product
+    }
+
+    /// Whether both have the same factors, in any order.
+    fn has_factors_of(&self, other: &Self) -> bool {
+        self.factors.len() == other.factors.len()
+            && self
+                .factors
+                .iter()
+                .all(|factor| other.factors.contains(factor))
+    }
+
+    fn negated(mut self) -> Self {
+        self.coefficient = self.coefficient.mul(Ratio::new(-1.0, 1.0));
+        self
+    }
+
+    /// Multiplies it by `expr` to the power `exponent`. Only integer powers
+    /// are taken into products and quotients, as `sqrt(x y)` is not
+    /// `sqrt(x) sqrt(y)` for negative `x` and `y`.
+    fn multiply(&mut self, expr: &Expr, exponent: &Expr) {
+        let integer = match exponent {
+            Expr::Number(value) if value.fract() == 0.0 && value.abs() <= f64::from(i32::MAX) => {
+                Some(*value as i32)
+            }
+            _ => None,
+        };
+        match (expr, integer) {
+            (_, Some(0)) => {}
+            (Expr::Number(value), Some(power)) if *value != 0.0 || power > 0 => {
+                self.coefficient = self.coefficient.mul(Ratio::new(*value, 1.0).powi(power));
+            }
+            (Expr::Neg(operand), Some(power)) => {
+                self.coefficient = self.coefficient.mul(Ratio::new(-1.0, 1.0).powi(power));
+                self.multiply(operand, exponent);
+            }
+            (Expr::Binary(BinaryOp::Mul, left, right), Some(_)) => {
+                self.multiply(left, exponent);
+                self.multiply(right, exponent);
+            }
+            (Expr::Binary(BinaryOp::Div, left, right), Some(power)) => {
+                self.multiply(left, exponent);
+                self.multiply(right, &number(-f64::from(power)));
+            }
+            // (b^p)^n is b^(pn) only where b^p is defined, which for
+            // fractional p is not where b is negative: sqrt(x)^2 stays as it is.
+            (Expr::Binary(BinaryOp::Pow, base, inner), Some(_)) => {
+                let inner = inner.simplified();
+                if is_integer(&inner) || is_non_negative(base) {
+                    let exponent = mul(inner, exponent.clone()).simplified();
+                    self.multiply(base, &exponent);
+                } else {
+                    // Negative powers as reciprocals, to go below the line.
+                    let (inner, exponent) = match Ratio::of(&inner) {
+                        Some(ratio) if ratio.is_negative() => (
+                            ratio.mul(Ratio::new(-1.0, 1.0)).to_expr(),
+                            neg(exponent.clone()).simplified(),
+                        ),
+                        _ => (inner, exponent.clone()),
+                    };
+                    let root = match Ratio::of(&inner) {
+                        Some(ratio) if ratio.numerator / ratio.denominator == 0.5 => {
+                            call(Function::Sqrt, base.simplified())
+                        }
+                        _ => pow(base.simplified(), inner),
+                    };
+                    self.push(root, exponent);
+                }
+            }
+            (Expr::Call(Function::Sqrt, argument), Some(_)) => {
+                if is_non_negative(argument) {
+                    let exponent = div(exponent.clone(), number(2.0)).simplified();
+                    self.multiply(argument, &exponent);
+                } else {
+                    self.push(
+                        call(Function::Sqrt, argument.simplified()),
+                        exponent.clone(),
+                    );
+                }
+            }
+            _ => {
+                let simplified = expr.simplified();
+                let is_product = matches!(
+                    simplified,
+                    Expr::Number(_)
+                        | Expr::Neg(_)
+                        | Expr::Binary(BinaryOp::Mul | BinaryOp::Div | BinaryOp::Pow, _, _)
+                );
+                if is_product && integer.is_some() && simplified != *expr {
+                    return self.multiply(&simplified, exponent);
+                }
+                self.push(simplified, exponent.clone());
+            }
+        }
+    }
+
+    /// Multiplies it by the simplified `base` to the power `exponent`, adding
+    /// the exponent to that of the same base unless that would cancel a base
+    /// which is undefined over whole intervals: sqrt(x) / sqrt(x) is not 1
+    /// for negative `x`.
+    fn push(&mut self, base: Expr, exponent: Expr) {
+        let sign = |exponent: &Expr| Ratio::of(exponent).map(Ratio::is_negative);
+        let cancels = |other: &Expr| sign(other).is_none() || sign(other) != sign(&exponent);
+        let same = self.factors.iter_mut().find(|(other_base, other)| {
+            *other_base == base && (is_defined_nearly_everywhere(&base) || !cancels(other))
+        });
+        match same {
+            Some((_, sum)) => *sum = add(sum.clone(), exponent).simplified(),
+            None => self.factors.push((base, exponent)),
+        }
+    }
+
+    /// As a quotient of products, with negative powers below the line and
+    /// powers of ½ as square roots.
+    fn to_expr(&self) -> Expr {
+        if self.coefficient.is_zero() {
+            return number(0.0);
+        }
+        if self.factors.is_empty() {
+            return self.coefficient.to_expr();
+        }
+        let (mut above, mut below) = (Vec::new(), Vec::new());
+        let Ratio {
+            numerator,
+            denominator,
+        } = self.coefficient;
+        if numerator.abs() != 1.0 {
+            above.push(number(numerator.abs()));
+        }
+        if denominator != 1.0 {
+            below.push(number(denominator));
+        }
+        let half = Ratio::new(1.0, 2.0);
+        // Powers of variables and constants first, as in `a·b^2·sin(b·x)`.
+        let mut factors: Vec<_> = self.factors.iter().collect();
+        factors.sort_by_key(|(base, _)| !matches!(base, Expr::Variable(_) | Expr::Constant(_)));
+        for (base, exponent) in factors {
+            let (list, exponent) = match Ratio::of(exponent) {
+                Some(ratio) if ratio.is_zero() => continue,
+                Some(ratio) if ratio.is_negative() => {
+                    (&mut below, ratio.mul(Ratio::new(-1.0, 1.0)))
+                }
+                Some(ratio) => (&mut above, ratio),
+                None => {
+                    above.push(pow(base.clone(), exponent.clone()));
+                    continue;
+                }
+            };
+            list.push(if exponent == Ratio::ONE {
+                base.clone()
+            } else if exponent == half {
+                call(Function::Sqrt, base.clone())
+            } else {
+                pow(base.clone(), exponent.to_expr())
+            });
+        }
+        let product = |factors: Vec<Expr>| factors.into_iter().reduce(mul);
+        let expr = match (product(above), product(below)) {
+            (above, None) => above.unwrap_or(number(1.0)),
+            (above, Some(below)) => div(above.unwrap_or(number(1.0)), below),
+        };
+        if numerator < 0.0 {
+            neg(expr)
+        } else {
+            expr
+        }
+    }
+}
+
+fn number(value: f64) -> Expr {
+    Expr::Number(value)
+}
+
+fn neg(operand: Expr) -> Expr {
+    Expr::Neg(Box::new(operand))
+}
+
+fn add(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Add, Box::new(left), Box::new(right))
+}
+
+fn sub(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Sub, Box::new(left), Box::new(right))
+}
+
+fn mul(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Mul, Box::new(left), Box::new(right))
+}
+
+fn div(left: Expr, right: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Div, Box::new(left), Box::new(right))
+}
+
+fn pow(base: Expr, exponent: Expr) -> Expr {
+    Expr::Binary(BinaryOp::Pow, Box::new(base), Box::new(exponent))
+}
+
+fn call(function: Function, argument: Expr) -> Expr {
+    Expr::Call(function, Box::new(argument))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::graph::Graph;
+    use super::super::parse::parse_graph;
+    use super::*;
+
+    fn expr(source: &str) -> Expr {
+        match parse_graph(source) {
+            Ok(Graph::Function(expr)) => expr,
+            other => panic!("{source:?} is not a function: {other:?}"),
+        }
+    }
+
+    fn derivative(source: &str) -> String {
+        expr(source).derivative("x").simplified().to_string()
+    }
+
+    #[test]
+    fn differentiates() {
+        assert_eq!(derivative("3x^4 - 2x^2 + 7"), "12x^3 - 4x");
+        assert_eq!(derivative("sin(x)^2"), "2 sin(x)·cos(x)");
+        assert_eq!(derivative("1/x"), "-1/x^2");
+        assert_eq!(derivative("0^x"), "0");
+        assert_eq!(derivative("x^x"), "x^x·(ln(x) + 1)");
+        assert_eq!(derivative("e^(2x)"), "2e^(2x)");
+        assert_eq!(derivative("x sin x"), "sin(x) + x·cos(x)");
+        assert_eq!(derivative("cos(3x)"), "-3 sin(3x)");
+        assert_eq!(derivative("(x^2 + 1)^3"), "6x·(x^2 + 1)^2");
+        assert_eq!(derivative("log x"), "1/(x·ln(10))");
+    }
+
+    #[test]
+    fn treats_other_variables_as_constants() {
+        assert_eq!(derivative("a x^2 + b x"), "2a·x + b");
+        assert_eq!(derivative("a^2"), "0");
+        assert!(!expr("a b").depends_on("x"));
+        assert!(expr("a x").depends_on("x"));
+    }
+
+    #[test]
+    fn simplifies() {
+        let simplified = |source: &str| expr(source).simplified().to_string();
+        assert_eq!(simplified("2x + 3x"), "5x");
+        assert_eq!(simplified("x - x"), "0");
+        assert_eq!(simplified("x/x"), "1");
+        assert_eq!(simplified("x^0.5"), "sqrt(x)");
+        assert_eq!(simplified("3x^4 - 2x^2 + 7"), "3x^4 - 2x^2 + 7");
+    }
+
+    #[test]
+    fn agrees_with_finite_differences() {
+        const H: f64 = 1e-6;
+        for source in [
+            "x^3 - 2x",
+            "sin(x)^2",
+            "x^x",
+            "e^(2x)/x",
+            "ln(x^2 + 1)",
+            "sqrt x",
+            "arctan(2x)",
+            "tan x",
+            "abs(x - 1)",
+        ] {
+            let (f, derivative) = (expr(source), expr(source).derivative("x").simplified());
+            for x in [0.3, 0.7, 1.6, 2.5] {
+                let at = |x: f64| f.eval(&[("x", x)]);
+                let expected = (at(x + H) - at(x - H)) / (2.0 * H);
+                let actual = derivative.eval(&[("x", x)]);
+                assert!(
+                    (actual - expected).abs() < 1e-4 * expected.abs().max(1.0),
+                    "d/dx {source} at {x}: {derivative} gives {actual}, not {expected}"
+                );
+            }
+        }
+    }
+}
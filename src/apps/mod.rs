@@ -0,0 +1,102 @@
+//! The tools that run in the windows of the desktop layout.
+//!
+//! Every tool implements [`MiniApp`] and is listed once in [`Registry::new`];
+//! the desktop finds apps by their id, so nothing else needs to know about them.
+//! Each app saves its state under its own storage key, `app.<id>`, apart from
+//! the state of the website.
+
+mod about;
+mod graphing_calculator;
+mod placeholder;
+mod projects;
+mod resume;
+
+/// A tool that runs in a window of the desktop layout.
+pub trait MiniApp {
+    /// Stable identifier, used to find the app and to store its state.
+    fn id(&self) -> &'static str;
+
+    /// The name in the current locale.
+    fn title(&self) -> &'static str;
+
+    fn icon(&self) -> &'static str;
+
+    fn ui(&mut self, ui: &mut egui::Ui);
+
+    /// Saves the state to keep between sessions, e.g. with [`eframe::set_value`].
+    fn save(&self, _storage: &mut dyn eframe::Storage, _key: &str) {}
+
+    /// Restores the state saved by [`MiniApp::save`], e.g. with [`eframe::get_value`].
+    fn load(&mut self, _storage: &dyn eframe::Storage, _key: &str) {}
+}
+
+/// All the apps, in the order the launcher lists them.
+#[derive(Default)]
+pub struct Registry {
+    apps: Vec<Box<dyn MiniApp>>,
+}
+
+impl Registry {
+    /// Every app, each with the state it saved last time.
+    pub fn new(storage: Option<&dyn eframe::Storage>) -> Self {
+        let mut registry = Self::default();
+        registry.register(Box::<about::About>::default(), storage);
+        registry.register(Box::<projects::Projects>::default(), storage);
+        registry.register(
+            Box::<graphing_calculator::GraphingCalculator>::default(),
+            storage,
+        );
+        registry.register(
+            Box::new(placeholder::Placeholder::new(
+                "note_maker",
+                "window.note_maker",
+                "📝",
+            )),
+            storage,
+        );
+        registry.register(
+            Box::new(placeholder::Placeholder::new(
+                "expense_calculator",
+                "window.expense_calculator",
+                "💰",
+            )),
+            storage,
+        );
+        registry.register(Box::<resume::Resume>::default(), storage);
+        registry
+    }
+
+    pub fn register(&mut self, mut app: Box<dyn MiniApp>, storage: Option<&dyn eframe::Storage>) {
+        debug_assert!(self.get(app.id()).is_none(), "two apps with the same id");
+        if let Some(storage) = storage {
+            app.load(storage, &storage_key(app.id()));
+        }
+        self.apps.push(app);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn MiniApp> {
+        self.apps.iter().map(|app| app.as_ref())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&dyn MiniApp> {
+        self.iter().find(|app| app.id() == id)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut (dyn MiniApp + 'static)> {
+        self.apps
+            .iter_mut()
+            .find(|app| app.id() == id)
+            .map(|app| app.as_mut())
+    }
+
+    /// Saves every app's state under its own key.
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        for app in &self.apps {
+            app.save(storage, &storage_key(app.id()));
+        }
+    }
+}
+
+fn storage_key(id: &str) -> String {
+    format!("app.{id}")
+}
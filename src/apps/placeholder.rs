@@ -0,0 +1,34 @@
+use crate::app::{add_text, TextType};
+use crate::i18n::tr;
+
+/// A tool that is not written yet.
+pub struct Placeholder {
+    id: &'static str,
+    /// Message key of the title.
+    title: &'static str,
+    icon: &'static str,
+}
+
+impl Placeholder {
+    pub fn new(id: &'static str, title: &'static str, icon: &'static str) -> Self {
+        Self { id, title, icon }
+    }
+}
+
+impl super::MiniApp for Placeholder {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn title(&self) -> &'static str {
+        tr(self.title)
+    }
+
+    fn icon(&self) -> &'static str {
+        self.icon
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.label(add_text(TextType::Paragraph, tr("desktop.coming_soon")).weak());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::app::{add_project, add_text, project_details_ui, TextType};
+use crate::content::{SectionBody, CONTENT};
+use crate::gallery::Gallery;
+use crate::i18n::tr;
+
+/// Every project, by section, with the details of one at a time.
+#[derive(Default)]
+pub struct Projects {
+    /// Id of the project whose details are shown instead of the list.
+    open: Option<String>,
+    gallery: Gallery,
+}
+
+impl super::MiniApp for Projects {
+    fn id(&self) -> &'static str {
+        "projects"
+    }
+
+    fn title(&self) -> &'static str {
+        tr("window.projects")
+    }
+
+    fn icon(&self) -> &'static str {
+        "📁"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        let content = &*CONTENT;
+        if let Some(project) = self.open.as_deref().and_then(|id| content.project(id)) {
+            if ui
+                .button(add_text(TextType::Paragraph, tr("window.back")))
+                .clicked()
+            {
+                self.open = None;
+            }
+            ui.heading(add_text(TextType::SubHeading, &project.name));
+            project_details_ui(
+                ui,
+                ("projects_app", &project.id),
+                project,
+                &mut self.gallery,
+                &[],
+            );
+            return;
+        }
+
+        for section in &content.sections {
+            let SectionBody::Projects { categories } = &section.body else {
+                continue;
+            };
+            ui.heading(add_text(TextType::SubHeading, section.localized_title()));
+            for project in content.projects_in(categories) {
+                let (_, open_details) = add_project(ui, project, &[]);
+                if open_details {
+                    self.open = Some(project.id.clone());
+                    self.gallery = Gallery::default();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::app::{add_custom_hyperlink, add_text, TextType};
+use crate::content::CONTENT;
+use crate::i18n::tr;
+use crate::resume::{self, Template};
+
+/// The résumé: a link to the published PDF, or one rendered in any template.
+#[derive(Default)]
+pub struct Resume {
+    template: Template,
+    /// Outcome of the last download.
+    status: Option<Result<String, String>>,
+}
+
+impl super::MiniApp for Resume {
+    fn id(&self) -> &'static str {
+        "resume"
+    }
+
+    fn title(&self) -> &'static str {
+        tr("window.resume")
+    }
+
+    fn icon(&self) -> &'static str {
+        "📄"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        let content = &*CONTENT;
+        add_custom_hyperlink(
+            ui,
+            add_text(TextType::Paragraph, tr("contact.resume")),
+            &content.resume_url(),
+        );
+        ui.add_space(10.0);
+        ui.horizontal_wrapped(|ui| {
+            egui::ComboBox::from_id_source("resume_template")
+                .selected_text(self.template.name())
+                .show_ui(ui, |ui| {
+                    for template in Template::ALL {
+                        ui.selectable_value(&mut self.template, template, template.name());
+                    }
+                })
+                .response
+                .on_hover_text(tr("resume.template"));
+            if ui
+                .button(add_text(TextType::Paragraph, tr("resume.download")))
+                .clicked()
+            {
+                let file_name = format!("{}_CV.pdf", content.profile.name.replace(' ', "_"));
+                self.status = Some(crate::download::save_file(
+                    &file_name,
+                    "application/pdf",
+                    &resume::render(self.template),
+                ));
+            }
+        });
+        match &self.status {
+            Some(Ok(message)) => {
+                ui.label(egui::RichText::new(message).weak());
+            }
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            }
+            None => {}
+        }
+    }
+
+    fn save(&self, storage: &mut dyn eframe::Storage, key: &str) {
+        eframe::set_value(storage, key, &self.template);
+    }
+
+    fn load(&mut self, storage: &dyn eframe::Storage, key: &str) {
+        if let Some(template) = eframe::get_value(storage, key) {
+            self.template = template;
+        }
+    }
+}
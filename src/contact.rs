@@ -0,0 +1,220 @@
+use crate::app::{add_custom_hyperlink, add_text, TextType};
+use crate::content::{ContactInfo, Content};
+use crate::i18n::tr;
+
+/// How much of the contact details to show.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ContactStyle {
+    /// Every detail on its own line, with the vCard and QR code exports.
+    Full,
+    /// A single row of links, for use inside other sections.
+    Compact,
+}
+
+/// The one widget that shows [`ContactInfo`], wherever contact details appear.
+#[derive(Default)]
+pub struct ContactCard {
+    show_qr_code: bool,
+    qr_code: Option<egui::TextureHandle>,
+    /// Outcome of the last vCard download.
+    status: Option<Result<String, String>>,
+}
+
+impl ContactCard {
+    pub fn ui(&mut self, ui: &mut egui::Ui, content: &Content, style: ContactStyle) {
+        let contact = &content.contact;
+        let resume = content.resume_url();
+        match style {
+            ContactStyle::Compact => {
+                ui.horizontal_wrapped(|ui| {
+                    for (label, url) in [
+                        (tr("contact.email"), contact.email_url().as_str()),
+                        (tr("contact.github"), &contact.github),
+                        (tr("contact.linkedin"), &contact.linkedin),
+                        (tr("contact.resume"), &resume),
+                    ] {
+                        add_custom_hyperlink(ui, add_text(TextType::Paragraph, label), url);
+                    }
+                });
+            }
+            ContactStyle::Full => {
+                let email = format!("{}: {}", tr("contact.email"), contact.email);
+                let phone = format!("{}: {}", tr("contact.phone"), contact.phone);
+                for (index, (label, url)) in [
+                    (email.as_str(), contact.email_url().as_str()),
+                    (&phone, &contact.phone_url()),
+                    (tr("contact.github"), &contact.github),
+                    (tr("contact.linkedin"), &contact.linkedin),
+                    (tr("contact.resume"), &resume),
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    if index > 0 {
+                        ui.add_space(10.0);
+                    }
+                    add_custom_hyperlink(ui, add_text(TextType::Paragraph, label), url);
+                }
+                ui.add_space(10.0);
+                self.export_ui(ui, content);
+            }
+        }
+    }
+
+    fn export_ui(&mut self, ui: &mut egui::Ui, content: &Content) {
+        ui.horizontal_wrapped(|ui| {
+            if ui
+                .button(add_text(TextType::Paragraph, tr("contact.save_vcard")))
+                .clicked()
+            {
+                let vcard = content.contact.to_vcard(&content.profile.name);
+                let file_name = format!("{}.vcf", content.profile.name.replace(' ', "_"));
+                self.status = Some(crate::download::save_file(
+                    &file_name,
+                    "text/vcard",
+                    vcard.as_bytes(),
+                ));
+            }
+            ui.toggle_value(
+                &mut self.show_qr_code,
+                add_text(TextType::Paragraph, tr("contact.qr_code")),
+            );
+        });
+        match &self.status {
+            Some(Ok(message)) => {
+                ui.label(egui::RichText::new(message).weak());
+            }
+            Some(Err(message)) => {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            }
+            None => {}
+        }
+
+        if self.show_qr_code {
+            let texture = self.qr_code.get_or_insert_with(|| {
+                let vcard = content.contact.to_vcard(&content.profile.name);
+                ui.ctx().load_texture(
+                    "contact_qr_code",
+                    qr_code_image(&vcard),
+                    egui::TextureOptions::NEAREST,
+                )
+            });
+            ui.add(
+                egui::Image::new(&*texture)
+                    .fit_to_exact_size(egui::vec2(200.0, 200.0))
+                    .maintain_aspect_ratio(true),
+            )
+            .on_hover_text(tr("contact.scan_hint"));
+        }
+    }
+}
+
+impl ContactInfo {
+    pub fn email_url(&self) -> String {
+        format!("mailto:{}", self.email)
+    }
+
+    pub fn phone_url(&self) -> String {
+        format!("tel:{}", self.phone.replace(' ', ""))
+    }
+
+    /// The contact details as a vCard 3.0, the format address books import.
+    pub fn to_vcard(&self, name: &str) -> String {
+        let (given, family) = name.rsplit_once(' ').unwrap_or((name, ""));
+        [
+            "BEGIN:VCARD".to_owned(),
+            "VERSION:3.0".to_owned(),
+            format!("N:{};{};;;", escape_vcard(family), escape_vcard(given)),
+            format!("FN:{}", escape_vcard(name)),
+            format!("EMAIL;TYPE=INTERNET:{}", escape_vcard(&self.email)),
+            format!("TEL;TYPE=CELL:{}", self.phone.replace(' ', "")),
+            format!("URL:{}", escape_vcard(&self.github)),
+            format!("URL:{}", escape_vcard(&self.linkedin)),
+            "END:VCARD".to_owned(),
+        ]
+        .join("\r\n")
+            + "\r\n"
+    }
+}
+
+fn escape_vcard(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Black modules on white, with the quiet zone the QR code spec asks for.
+fn qr_code_image(text: &str) -> egui::ColorImage {
+    const QUIET_ZONE: i32 = 4;
+
+    let qr = qrcodegen::QrCode::encode_text(text, qrcodegen::QrCodeEcc::Medium)
+        .expect("contact details should fit in a QR code");
+    let size = qr.size() + 2 * QUIET_ZONE;
+    let pixels = (0..size)
+        .flat_map(|y| (0..size).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            if qr.get_module(x - QUIET_ZONE, y - QUIET_ZONE) {
+                egui::Color32::BLACK
+            } else {
+                egui::Color32::WHITE
+            }
+        })
+        .collect();
+    egui::ColorImage {
+        size: [size as usize; 2],
+        pixels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact() -> ContactInfo {
+        ContactInfo {
+            email: "adarsh@example.com".to_owned(),
+            phone: "+91 85278 59660".to_owned(),
+            github: "https://github.com/Saphereye".to_owned(),
+            linkedin: "https://www.linkedin.com/in/adarsh".to_owned(),
+            resume: "resume.pdf".to_owned(),
+        }
+    }
+
+    #[test]
+    fn writes_a_vcard() {
+        assert_eq!(
+            contact().to_vcard("Adarsh Das"),
+            "BEGIN:VCARD\r\n\
+             VERSION:3.0\r\n\
+             N:Das;Adarsh;;;\r\n\
+             FN:Adarsh Das\r\n\
+             EMAIL;TYPE=INTERNET:adarsh@example.com\r\n\
+             TEL;TYPE=CELL:+918527859660\r\n\
+             URL:https://github.com/Saphereye\r\n\
+             URL:https://www.linkedin.com/in/adarsh\r\n\
+             END:VCARD\r\n"
+        );
+    }
+
+    #[test]
+    fn escapes_vcard_values() {
+        assert_eq!(escape_vcard(r"a,b;c\d"), r"a\,b\;c\\d");
+        assert_eq!(escape_vcard("two\nlines"), r"two\nlines");
+
+        let vcard = contact().to_vcard("Das, Adarsh; Jr.");
+        assert!(
+            vcard.contains("\r\nN:Jr.;Das\\, Adarsh\\;;;;\r\n"),
+            "{vcard}"
+        );
+        assert!(vcard.contains("\r\nFN:Das\\, Adarsh\\; Jr.\r\n"), "{vcard}");
+    }
+
+    #[test]
+    fn names_without_a_family_name() {
+        assert!(contact()
+            .to_vcard("Adarsh")
+            .contains("\r\nN:;Adarsh;;;\r\n"));
+    }
+}
@@ -0,0 +1,545 @@
+use std::collections::BTreeSet;
+
+/// The portfolio content, deserialized from `assets/content.json`.
+///
+/// The file is embedded at build time, so editing the portfolio only means
+/// editing that file. Everything in here is plain data: rendering lives in `app.rs`.
+#[derive(serde::Deserialize, Debug)]
+pub struct Content {
+    pub profile: Profile,
+    /// Sections of the main page, in display order.
+    pub sections: Vec<Section>,
+    /// Sections of the side panel, in display order.
+    pub sidebar: Vec<Section>,
+    pub projects: Vec<Project>,
+    pub skills: Vec<Skill>,
+    pub courses: Vec<Course>,
+    /// Schools and universities, most recent first.
+    pub education: Vec<Education>,
+    pub achievements: Vec<Achievement>,
+    pub contact: ContactInfo,
+    /// Dated events besides projects, shown on the timeline.
+    pub milestones: Vec<Milestone>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Profile {
+    pub name: String,
+    /// Shown below the name on the résumé, e.g. "Software Engineer".
+    pub headline: String,
+    /// Where I live, e.g. "Noida, India".
+    pub location: String,
+    /// One sentence about the site, for search results and link previews.
+    pub description: String,
+    /// Where the site is published, ending in a slash.
+    pub site_url: String,
+    /// Base URL that document paths such as `career_docs/...` are relative to.
+    pub documents_url: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Section {
+    /// Stable identifier, also used as the anchor of the section.
+    pub id: String,
+    pub title: String,
+    #[serde(flatten)]
+    pub body: SectionBody,
+}
+
+impl Section {
+    /// The title in the current locale, from the `section.<id>` message if there is one.
+    pub fn localized_title(&self) -> &str {
+        crate::i18n::tr_or(&format!("section.{}", self.id), &self.title)
+    }
+}
+
+/// What a section shows below its heading.
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SectionBody {
+    Text {
+        paragraphs: Vec<Paragraph>,
+        /// Show a row of contact links below the text.
+        #[serde(default)]
+        contact_links: bool,
+    },
+    Skills,
+    Courses,
+    /// Every entry of `Content::education`.
+    Education,
+    Achievements,
+    /// All projects in any of the given categories, most recent first.
+    Projects {
+        categories: Vec<Category>,
+    },
+    Contacts,
+}
+
+/// Either a plain string or a sequence of styled spans laid out on one wrapped line.
+#[derive(serde::Deserialize, Debug)]
+#[serde(untagged)]
+pub enum Paragraph {
+    Plain(String),
+    Spans(Vec<Span>),
+}
+
+impl Paragraph {
+    /// The text without any styling, with spaces between the spans except before punctuation.
+    pub fn text(&self) -> String {
+        match self {
+            Self::Plain(text) => text.clone(),
+            Self::Spans(spans) => spans.iter().fold(String::new(), |mut text, span| {
+                let punctuation = span.text.starts_with([',', '.', ';', ':', '!', '?', ')']);
+                if !text.is_empty() && !punctuation {
+                    text.push(' ');
+                }
+                text.push_str(&span.text);
+                text
+            }),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Span {
+    pub text: String,
+    #[serde(default)]
+    pub underline: bool,
+    #[serde(default)]
+    pub link: Option<String>,
+}
+
+/// How to reach me. Everything that shows contact details renders this, so it
+/// only needs to be kept up to date in one place.
+#[derive(serde::Deserialize, Debug)]
+pub struct ContactInfo {
+    pub email: String,
+    /// In international format, e.g. "+91 85278 59660".
+    pub phone: String,
+    pub github: String,
+    pub linkedin: String,
+    /// Path of the résumé relative to `Profile::site_url`. The site build
+    /// renders it there from this file; see `src/resume.rs`.
+    pub resume: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Skill {
+    /// Matches the names in `Project::tech`.
+    pub name: String,
+    /// Self-assessed, from 1 (basics) to 5 (expert). `None` until rated.
+    #[serde(default)]
+    pub proficiency: Option<u8>,
+    /// The year I started using it, if recorded.
+    #[serde(default)]
+    pub since: Option<u16>,
+}
+
+impl Skill {
+    pub const MAX_PROFICIENCY: u8 = 5;
+
+    /// Whole years since I started using it, counting the current one.
+    pub fn years(&self) -> Option<u16> {
+        Some((YearMonth::current().year + 1).saturating_sub(self.since?))
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Project {
+    /// Stable identifier, unique among projects.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: Category,
+    #[serde(default)]
+    pub status: Status,
+    /// What I did on the project, e.g. "Research Intern".
+    #[serde(default)]
+    pub role: Option<String>,
+    /// `None` if not recorded; undated projects are left off the timeline.
+    #[serde(default)]
+    pub start: Option<YearMonth>,
+    /// `None` while the project is still going on, or if not recorded.
+    #[serde(default)]
+    pub end: Option<YearMonth>,
+    /// Languages, frameworks and tools used, matching the names in `Content::skills`.
+    #[serde(default)]
+    pub tech: Vec<String>,
+    #[serde(default)]
+    pub links: ProjectLinks,
+    /// Screenshots and figures, the first one doubling as the thumbnail.
+    #[serde(default)]
+    pub images: Vec<ProjectImage>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct ProjectImage {
+    /// Path relative to `assets/`.
+    pub path: String,
+    #[serde(default)]
+    pub caption: Option<String>,
+}
+
+impl Project {
+    pub fn links_count(&self) -> usize {
+        [&self.links.source, &self.links.demo, &self.links.paper]
+            .into_iter()
+            .filter(|link| link.is_some())
+            .count()
+    }
+
+    /// The link the project name points to: the source if there is one, otherwise the demo or paper.
+    pub fn main_link(&self) -> Option<&str> {
+        self.links
+            .source
+            .as_deref()
+            .or(self.links.demo.as_deref())
+            .or(self.links.paper.as_deref())
+    }
+
+    /// Whether the project uses at least one of the given skills.
+    pub fn uses_any(&self, skills: &BTreeSet<String>) -> bool {
+        self.tech.iter().any(|tech| skills.contains(tech))
+    }
+
+    /// E.g. "Mar 2023 – May 2023", or "Mar 2023 – Present" for ongoing projects.
+    /// `None` if the project is undated.
+    pub fn duration(&self) -> Option<String> {
+        let start = self.start?;
+        Some(match self.end {
+            Some(end) if end == start => start.to_string(),
+            Some(end) => format!("{start} – {end}"),
+            None if self.status == Status::Ongoing => format!("{start} – Present"),
+            None => start.to_string(),
+        })
+    }
+}
+
+#[derive(serde::Deserialize, Debug, Default)]
+pub struct ProjectLinks {
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub demo: Option<String>,
+    /// A paper, report or article written about the project.
+    #[serde(default)]
+    pub paper: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Research,
+    Work,
+    Hobby,
+    Article,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Completed,
+    Ongoing,
+    Archived,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Completed => "Completed",
+            Self::Ongoing => "Ongoing",
+            Self::Archived => "Archived",
+        })
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Course {
+    /// Stable identifier, unique among courses.
+    pub id: String,
+    pub name: String,
+    /// Course code in the BITS catalogue, e.g. "CS F211", if recorded.
+    #[serde(default)]
+    pub code: Option<String>,
+    /// When I took the course, e.g. "Year 2, Semester 1", if recorded.
+    #[serde(default)]
+    pub semester: Option<String>,
+    #[serde(default)]
+    pub grade: Option<String>,
+    pub syllabus: String,
+    /// Ids of projects done as part of, or building on, the course.
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub reports: Vec<Report>,
+}
+
+impl Course {
+    /// The code and semester, as far as they are recorded, e.g. "CS F211 · Year 2, Semester 1".
+    pub fn details(&self) -> Option<String> {
+        let details: Vec<&str> = [&self.code, &self.semester]
+            .into_iter()
+            .filter_map(|detail| detail.as_deref())
+            .collect();
+        (!details.is_empty()).then(|| details.join(" · "))
+    }
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Report {
+    pub title: String,
+    /// Path relative to `Profile::documents_url`, e.g. `career_docs/DL_Report.pdf`.
+    pub path: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Education {
+    /// E.g. "B.E. Hons in Computer Science".
+    pub degree: String,
+    pub institution: String,
+    /// E.g. "Hyderabad, India".
+    pub location: String,
+    pub start: YearMonth,
+    /// `None` while still studying there.
+    #[serde(default)]
+    pub end: Option<YearMonth>,
+    /// E.g. "CGPA: 7.75".
+    #[serde(default)]
+    pub grades: Vec<String>,
+}
+
+impl Education {
+    /// E.g. "2021 – 2025", or "2021 – Present".
+    pub fn duration(&self) -> String {
+        match self.end {
+            Some(end) => format!("{} – {end}", self.start),
+            None => format!("{} – Present", self.start),
+        }
+    }
+}
+
+/// An award or placing, e.g. in a hackathon.
+#[derive(serde::Deserialize, Debug)]
+pub struct Achievement {
+    pub title: String,
+    /// Id of the project it was won with, if any.
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct Milestone {
+    pub title: String,
+    pub kind: MilestoneKind,
+    pub start: YearMonth,
+    /// `None` while still going on.
+    #[serde(default)]
+    pub end: Option<YearMonth>,
+    /// Section or project id with the details.
+    pub anchor: String,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MilestoneKind {
+    Education,
+    Internship,
+    Competition,
+    Club,
+}
+
+/// A month of a year, written as `"YYYY-MM"` in the content file, or just a
+/// year (`"YYYY"`) where the month is not known.
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String")]
+pub struct YearMonth {
+    pub year: u16,
+    /// 1 to 12.
+    pub month: Option<u8>,
+}
+
+impl TryFrom<String> for YearMonth {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("expected a date like \"2023-05\" or \"2023\", got {value:?}");
+        let (year, month) = match value.split_once('-') {
+            Some((year, month)) => (year, Some(month)),
+            None => (value.as_str(), None),
+        };
+        let year = year.parse().map_err(|_| invalid())?;
+        let month = match month {
+            Some(month) => Some(month.parse().map_err(|_| invalid())?),
+            None => None,
+        };
+        if month.map_or(false, |month| !(1..=12).contains(&month)) {
+            return Err(invalid());
+        }
+        Ok(Self { year, month })
+    }
+}
+
+impl YearMonth {
+    pub fn current() -> Self {
+        let (year, month, _) = crate::time::civil_from_days(crate::time::unix_day());
+        Self {
+            year: year as u16,
+            month: Some(month as u8),
+        }
+    }
+
+    /// The start of the month (or year) as a fractional year, e.g. 2023.25 for April 2023.
+    pub fn as_year(self) -> f64 {
+        f64::from(self.year) + f64::from(self.month.unwrap_or(1) - 1) / 12.0
+    }
+
+    /// The end of the month (or year) as a fractional year.
+    pub fn end_as_year(self) -> f64 {
+        match self.month {
+            Some(_) => self.as_year() + 1.0 / 12.0,
+            None => f64::from(self.year + 1),
+        }
+    }
+
+    /// In ISO 8601, e.g. "2023-05" or "2023".
+    pub fn iso(self) -> String {
+        match self.month {
+            Some(month) => format!("{:04}-{month:02}", self.year),
+            None => format!("{:04}", self.year),
+        }
+    }
+}
+
+impl std::fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+        match self.month {
+            Some(month) => write!(f, "{} {}", MONTHS[month as usize - 1], self.year),
+            None => write!(f, "{}", self.year),
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// The content embedded in the binary.
+    pub static ref CONTENT: Content = Content::from_json(include_str!("../assets/content.json"))
+        .expect("assets/content.json should be valid portfolio content");
+}
+
+impl Content {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn project(&self, id: &str) -> Option<&Project> {
+        self.projects.iter().find(|project| project.id == id)
+    }
+
+    pub fn course(&self, id: &str) -> Option<&Course> {
+        self.courses.iter().find(|course| course.id == id)
+    }
+
+    /// Full URL of a document stored next to the site, e.g. a course report.
+    pub fn document_url(&self, path: &str) -> String {
+        format!("{}{}", self.profile.documents_url, path.replace(' ', "%20"))
+    }
+
+    /// Full URL of the résumé the site build renders.
+    pub fn resume_url(&self) -> String {
+        format!("{}{}", self.profile.site_url, self.contact.resume)
+    }
+
+    /// Number of projects using the given skill.
+    pub fn project_count(&self, skill: &str) -> usize {
+        self.projects
+            .iter()
+            .filter(|project| project.tech.iter().any(|tech| tech == skill))
+            .count()
+    }
+
+    /// Projects in any of the given categories, most recent first.
+    pub fn projects_in(&self, categories: &[Category]) -> Vec<&Project> {
+        let mut projects: Vec<&Project> = self
+            .projects
+            .iter()
+            .filter(|project| categories.contains(&project.category))
+            .collect();
+        projects.sort_by(|a, b| b.start.cmp(&a.start));
+        projects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<YearMonth, String> {
+        YearMonth::try_from(value.to_owned())
+    }
+
+    #[test]
+    fn parses_year_and_month() {
+        let date = parse("2023-05").unwrap();
+        assert_eq!(
+            date,
+            YearMonth {
+                year: 2023,
+                month: Some(5)
+            }
+        );
+        assert_eq!(date.iso(), "2023-05");
+        assert_eq!(date.to_string(), "May 2023");
+    }
+
+    #[test]
+    fn parses_year_alone() {
+        let date = parse("2021").unwrap();
+        assert_eq!(
+            date,
+            YearMonth {
+                year: 2021,
+                month: None
+            }
+        );
+        assert_eq!(date.iso(), "2021");
+        assert_eq!(date.to_string(), "2021");
+        assert_eq!(date.as_year(), 2021.0);
+        assert_eq!(date.end_as_year(), 2022.0);
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for value in ["", "2023-", "2023-00", "2023-13", "May 2023", "2023-05-01"] {
+            assert!(parse(value).is_err(), "{value:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn orders_dates() {
+        assert!(parse("2022-12").unwrap() < parse("2023-01").unwrap());
+        assert!(parse("2023").unwrap() < parse("2023-01").unwrap());
+    }
+
+    #[test]
+    fn embedded_content_is_valid() {
+        let content = Content::from_json(include_str!("../assets/content.json")).unwrap();
+        assert!(!content.projects.is_empty());
+
+        let mut ids = BTreeSet::new();
+        for project in &content.projects {
+            assert!(
+                ids.insert(&project.id),
+                "duplicate project {:?}",
+                project.id
+            );
+        }
+        for achievement in &content.achievements {
+            if let Some(id) = &achievement.project {
+                assert!(content.project(id).is_some(), "unknown project {id:?}");
+            }
+        }
+    }
+}
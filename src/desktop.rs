@@ -0,0 +1,209 @@
+//! A desktop-style shell: a taskbar that opens each [`MiniApp`] as a movable,
+//! resizable window that can be minimised to the taskbar or closed.
+//!
+//! The open windows, their stacking order and where they are all persist with
+//! the rest of the app state.
+
+use crate::apps::{MiniApp, Registry};
+use crate::i18n::tr;
+
+fn window_id(app: &str) -> egui::Id {
+    egui::Id::new(("desktop_window", app))
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct OpenWindow {
+    /// Id of the app shown in the window.
+    app: String,
+    minimized: bool,
+    /// Top left corner of the window, once it has been shown.
+    pos: Option<[f32; 2]>,
+    /// Size of the window's contents, once it has been shown.
+    size: Option<[f32; 2]>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+pub struct Desktop {
+    /// The open windows, from back to front.
+    open_apps: Vec<OpenWindow>,
+    /// Whether egui's stacking order has been set from `open_apps` since the app started.
+    #[serde(skip)]
+    restored_order: bool,
+}
+
+impl Desktop {
+    /// Opens the app's window, or brings it to the front if it is already open.
+    pub fn open(&mut self, app: &str) {
+        match self.index(app) {
+            Some(index) => {
+                let mut open = self.open_apps.remove(index);
+                open.minimized = false;
+                self.open_apps.push(open);
+            }
+            None => self.open_apps.push(OpenWindow {
+                app: app.to_owned(),
+                minimized: false,
+                pos: None,
+                size: None,
+            }),
+        }
+    }
+
+    fn index(&self, app: &str) -> Option<usize> {
+        self.open_apps.iter().position(|open| open.app == app)
+    }
+
+    /// The app whose window is at the front, unless it is minimised.
+    fn front(&self) -> Option<&str> {
+        self.open_apps
+            .iter()
+            .rev()
+            .find(|open| !open.minimized)
+            .map(|open| open.app.as_str())
+    }
+
+    /// Shows the taskbar along the bottom of `ui` and the windows of the apps above it.
+    pub fn ui(&mut self, ui: &mut egui::Ui, apps: &mut Registry) {
+        // Apps that are no longer registered, e.g. after an update.
+        self.open_apps.retain(|open| apps.get(&open.app).is_some());
+        let mut raise = None;
+        egui::TopBottomPanel::bottom("taskbar").show_inside(ui, |ui| {
+            raise = self.taskbar_ui(ui, apps);
+        });
+        let area = ui.available_rect_before_wrap();
+        ui.allocate_rect(area, egui::Sense::hover());
+
+        let ctx = ui.ctx().clone();
+        let mut closed = Vec::new();
+        let mut clicked = None;
+        for (index, open) in self.open_apps.iter_mut().enumerate() {
+            if open.minimized {
+                continue;
+            }
+            let Some(app) = apps.get_mut(&open.app) else {
+                continue;
+            };
+            let id = window_id(&open.app);
+            let mut egui_window = egui::Window::new(app.title())
+                .id(id)
+                .title_bar(false)
+                .constraint_to(area)
+                .default_size([500.0, 400.0])
+                .default_pos(area.min + egui::vec2(30.0, 30.0) * index as f32);
+            if let Some(pos) = open.pos {
+                egui_window = egui_window.default_pos(pos);
+            }
+            if let Some(size) = open.size {
+                egui_window = egui_window.default_size(size);
+            }
+            let response = egui_window.show(&ctx, |ui| {
+                open.size = Some(ui.max_rect().size().into());
+                let (minimize, close) = title_bar_ui(ui, app);
+                if minimize {
+                    open.minimized = true;
+                }
+                if close {
+                    closed.push(open.app.clone());
+                }
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_source(id)
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| app.ui(ui));
+            });
+            if let Some(response) = response {
+                let response = response.response;
+                open.pos = Some(response.rect.min.into());
+                // egui brings a window to the front when it is pressed; keep `open_apps` in the same order.
+                let pressed_on = ctx.input(|input| {
+                    input
+                        .pointer
+                        .press_origin()
+                        .filter(|_| input.pointer.any_pressed())
+                });
+                if pressed_on.and_then(|pos| ctx.layer_id_at(pos)) == Some(response.layer_id) {
+                    clicked = Some(open.app.clone());
+                }
+            }
+        }
+
+        self.open_apps.retain(|open| !closed.contains(&open.app));
+        let raised = raise.is_some();
+        if let Some(app) = raise.or(clicked) {
+            self.open(&app);
+            if raised {
+                ctx.move_to_top(egui::LayerId::new(egui::Order::Middle, window_id(&app)));
+            }
+        }
+        if !self.restored_order {
+            for open in &self.open_apps {
+                ctx.move_to_top(egui::LayerId::new(
+                    egui::Order::Middle,
+                    window_id(&open.app),
+                ));
+            }
+            self.restored_order = true;
+        }
+    }
+
+    /// The launcher and a button for every open window. Returns the app to bring to the front.
+    fn taskbar_ui(&mut self, ui: &mut egui::Ui, apps: &Registry) -> Option<String> {
+        let mut raise = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.menu_button(format!("☰ {}", tr("desktop.start")), |ui| {
+                for app in apps.iter() {
+                    if ui.button(label(app)).clicked() {
+                        raise = Some(app.id().to_owned());
+                        ui.close_menu();
+                    }
+                }
+            });
+            ui.separator();
+            let front = self.front().map(str::to_owned);
+            for open in &mut self.open_apps {
+                let Some(app) = apps.get(&open.app) else {
+                    continue;
+                };
+                let is_front = front.as_deref() == Some(app.id());
+                let button = ui
+                    .selectable_label(is_front, label(app))
+                    .on_hover_text(tr("desktop.taskbar_hint"));
+                if button.clicked() {
+                    // Like on a desktop: minimise the front window, bring up any other.
+                    if is_front {
+                        open.minimized = true;
+                    } else {
+                        raise = Some(open.app.clone());
+                    }
+                }
+            }
+        });
+        raise
+    }
+}
+
+/// The title of the window with minimise and close buttons. Returns whether each was clicked.
+fn title_bar_ui(ui: &mut egui::Ui, app: &dyn MiniApp) -> (bool, bool) {
+    let mut minimize = false;
+    let mut close = false;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(label(app)).strong());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            close = ui
+                .small_button("🗙")
+                .on_hover_text(tr("desktop.close"))
+                .clicked();
+            minimize = ui
+                .small_button("🗕")
+                .on_hover_text(tr("desktop.minimize"))
+                .clicked();
+        });
+    });
+    (minimize, close)
+}
+
+/// The icon and title of the app.
+fn label(app: &dyn MiniApp) -> String {
+    format!("{} {}", app.icon(), app.title())
+}
@@ -0,0 +1,39 @@
+//! Handing generated files to the user.
+
+/// Offers a file to the user: a browser download on the web, a file in the
+/// working directory natively. On success, returns where the file went.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(file_name: &str, _mime: &str, bytes: &[u8]) -> Result<String, String> {
+    std::fs::write(file_name, bytes).map_err(|err| format!("Failed to save {file_name}: {err}"))?;
+    let path = std::fs::canonicalize(file_name).unwrap_or_else(|_| file_name.into());
+    Ok(format!("Saved to {}", path.display()))
+}
+
+/// Offers a file to the user: a browser download on the web, a file in the
+/// working directory natively. On success, returns where the file went.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(file_name: &str, mime: &str, bytes: &[u8]) -> Result<String, String> {
+    use wasm_bindgen::JsCast;
+
+    let failed = |err: wasm_bindgen::JsValue| format!("Failed to download {file_name}: {err:?}");
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| format!("Failed to download {file_name}: no document"))?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime);
+    let blob =
+        web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(failed)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(failed)?;
+
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(failed)?
+        .unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url).map_err(failed)?;
+    Ok(format!("Downloaded {file_name}"))
+}
@@ -0,0 +1,486 @@
+//! A static HTML version of the portfolio for crawlers, link previews and
+//! browsers without WebAssembly.
+//!
+//! The app draws everything on a `<canvas>`, which search engines cannot read.
+//! `cargo run -- export <dir>` (run by the post-build hook in `Trunk.toml`)
+//! fills the built `index.html` with OpenGraph tags, schema.org JSON-LD and the
+//! content as semantic HTML, and writes a sitemap and the project images next to it.
+
+use std::fmt::Write as _;
+
+use crate::content::{Content, Project, SectionBody, CONTENT};
+use crate::images::ImageAsset;
+
+/// Where in `index.html` the static content goes.
+pub const CONTENT_MARKER: &str = "<!-- static-content -->";
+
+/// The `index.html` with the static content filled in.
+pub fn static_page(index_html: &str) -> Result<String, String> {
+    let content = &*CONTENT;
+    if !index_html.contains(CONTENT_MARKER) {
+        return Err(format!("index.html has no {CONTENT_MARKER} marker"));
+    }
+    let head_end = index_html
+        .find("</head>")
+        .ok_or("index.html has no </head>")?;
+    let mut page = String::with_capacity(index_html.len() + 64 * 1024);
+    page.push_str(&index_html[..head_end]);
+    page.push_str(&head(content));
+    page.push_str(&index_html[head_end..]);
+    Ok(page.replacen(CONTENT_MARKER, &body(content), 1))
+}
+
+/// A sitemap listing the page and the documents it links to that are on the
+/// same host, as a sitemap may list no others.
+///
+/// There is no `<lastmod>`: the build date would claim every page changed on
+/// every deploy, and the content has no dates of its own for them.
+pub fn sitemap() -> String {
+    let content = &*CONTENT;
+    let site_host = host(&content.profile.site_url);
+    let mut urls = vec![content.profile.site_url.clone()];
+    urls.push(content.resume_url());
+    for course in &content.courses {
+        urls.extend(
+            course
+                .reports
+                .iter()
+                .map(|report| content.document_url(&report.path)),
+        );
+    }
+
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for url in urls.iter().filter(|url| host(url) == site_host) {
+        writeln!(sitemap, "  <url><loc>{}</loc></url>", escape(url)).unwrap();
+    }
+    sitemap.push_str("</urlset>\n");
+    sitemap
+}
+
+/// The host of an absolute URL, like `saphereye.github.io`.
+fn host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    rest.split(['/', '?', '#']).next()
+}
+
+/// `robots.txt` pointing crawlers at the sitemap.
+pub fn robots_txt() -> String {
+    format!(
+        "User-agent: *\nAllow: /\nSitemap: {}sitemap.xml\n",
+        CONTENT.profile.site_url
+    )
+}
+
+/// Link preview tags, JSON-LD and the style of the static content.
+fn head(content: &Content) -> String {
+    let profile = &content.profile;
+    let image = format!("{}icon-1024.png", profile.site_url);
+    let mut head = String::new();
+    writeln!(
+        head,
+        "    <link rel=\"canonical\" href=\"{}\">",
+        escape(&profile.site_url)
+    )
+    .unwrap();
+    for (property, value) in [
+        ("og:type", "profile"),
+        ("og:title", &profile.name),
+        ("og:description", &profile.description),
+        ("og:url", &profile.site_url),
+        ("og:image", &image),
+        ("twitter:card", "summary"),
+    ] {
+        writeln!(
+            head,
+            "    <meta property=\"{property}\" content=\"{}\">",
+            escape(value)
+        )
+        .unwrap();
+    }
+    // "</" cannot appear inside a script element.
+    let json_ld = serde_json::to_string_pretty(&json_ld(content))
+        .expect("JSON-LD is serializable")
+        .replace("</", "<\\/");
+    writeln!(
+        head,
+        "    <script type=\"application/ld+json\">\n{json_ld}\n    </script>"
+    )
+    .unwrap();
+    head.push_str(STYLE);
+    head
+}
+
+const STYLE: &str = r#"    <style>
+        .static-content {
+            position: absolute;
+            inset: 0;
+            overflow: auto;
+            padding: 1em max(1em, calc(50% - 25em));
+            color: #202020;
+            background: #f8f8f8;
+            font-family: Ubuntu-Light, Helvetica, sans-serif;
+            line-height: 1.5;
+        }
+
+        .static-content img {
+            max-width: 100%;
+            height: auto;
+        }
+
+        @media (prefers-color-scheme: dark) {
+            .static-content {
+                color: #e0e0e0;
+                background: #1b1b1b;
+            }
+
+            .static-content a {
+                color: #8ab4f8;
+            }
+        }
+    </style>
+"#;
+
+/// A schema.org `Person` and a `CreativeWork` for every project.
+fn json_ld(content: &Content) -> serde_json::Value {
+    let profile = &content.profile;
+    let contact = &content.contact;
+    let person_id = format!("{}#person", profile.site_url);
+    let person = serde_json::json!({
+        "@type": "Person",
+        "@id": person_id,
+        "name": profile.name,
+        "url": profile.site_url,
+        "description": profile.description,
+        "email": contact.email_url(),
+        "telephone": contact.phone,
+        "sameAs": [contact.github, contact.linkedin],
+        "knowsAbout": content.skills.iter().map(|skill| &skill.name).collect::<Vec<_>>(),
+    });
+    let works = content.projects.iter().map(|project| {
+        let mut work = serde_json::json!({
+            "@type": "CreativeWork",
+            "@id": project_url(content, project),
+            "name": project.name,
+            "description": crate::markdown::plain_text(&project.description),
+            "author": { "@id": person_id },
+            "keywords": project.tech.join(", "),
+        });
+        if let Some(start) = project.start {
+            work["dateCreated"] = start.iso().into();
+        }
+        if let Some(url) = project.main_link() {
+            work["url"] = url.into();
+        }
+        if let Some(asset) = project
+            .images
+            .first()
+            .and_then(|image| ImageAsset::find(&image.path))
+        {
+            work["image"] = format!("{}{}", profile.site_url, published_path(asset)).into();
+        }
+        work
+    });
+    serde_json::json!({
+        "@context": "https://schema.org",
+        "@graph": std::iter::once(person).chain(works).collect::<Vec<_>>(),
+    })
+}
+
+/// The address of the project on the site; the app scrolls to it.
+fn project_url(content: &Content, project: &Project) -> String {
+    format!("{}#{}", content.profile.site_url, project.id)
+}
+
+/// The content as HTML, plus a script that hides it while the app loads.
+fn body(content: &Content) -> String {
+    let mut html = String::from("<main id=\"static_content\" class=\"static-content\">\n");
+    content_html(&mut html, content, true);
+    html.push_str("</main>\n");
+    html.push_str(
+        r#"<script>
+        // Browsers that can run the app show the loading screen instead; main.rs
+        // removes this text version once the app has started.
+        if (typeof WebAssembly === "object") {
+            document.getElementById("static_content").hidden = true;
+        }
+    </script>"#,
+    );
+    html
+}
+
+/// A standalone page of the content without images, black on white, for printing.
+pub fn print_document() -> String {
+    let content = &*CONTENT;
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n{PRINT_STYLE}</head>\n<body>\n",
+        escape(&content.profile.name),
+    );
+    content_html(&mut html, content, false);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+const PRINT_STYLE: &str = r#"<style>
+    body {
+        max-width: 45em;
+        margin: 0 auto;
+        color: black;
+        background: white;
+        font: 11pt/1.45 Georgia, serif;
+    }
+
+    a {
+        color: black;
+    }
+
+    h2 {
+        border-bottom: 1px solid black;
+    }
+
+    article,
+    h3 {
+        break-inside: avoid;
+    }
+
+    pre {
+        white-space: pre-wrap;
+    }
+</style>
+"#;
+
+/// The header and every section, optionally with the project images.
+fn content_html(html: &mut String, content: &Content, images: bool) {
+    let profile = &content.profile;
+    let contact = &content.contact;
+    writeln!(
+        html,
+        "<header>\n<h1>{}</h1>\n<p>{}</p>",
+        escape(&profile.name),
+        escape(&profile.description),
+    )
+    .unwrap();
+    html.push_str("<nav><ul>\n");
+    for (text, url) in [
+        (contact.email.clone(), contact.email_url()),
+        (contact.phone.clone(), contact.phone_url()),
+        ("GitHub".to_owned(), contact.github.clone()),
+        ("LinkedIn".to_owned(), contact.linkedin.clone()),
+        ("Résumé".to_owned(), content.resume_url()),
+    ] {
+        writeln!(html, "<li>{}</li>", link(&url, &escape(&text))).unwrap();
+    }
+    html.push_str("</ul></nav>\n</header>\n");
+
+    for section in content.sections.iter().chain(&content.sidebar) {
+        writeln!(
+            html,
+            "<section id=\"{}\">\n<h2>{}</h2>",
+            escape(&section.id),
+            escape(&section.title),
+        )
+        .unwrap();
+        match &section.body {
+            SectionBody::Text { paragraphs, .. } => {
+                for paragraph in paragraphs {
+                    writeln!(html, "<p>{}</p>", escape(&paragraph.text())).unwrap();
+                }
+            }
+            SectionBody::Skills => {
+                html.push_str("<ul>\n");
+                for skill in &content.skills {
+                    match skill.since {
+                        Some(since) => {
+                            writeln!(html, "<li>{} (since {since})</li>", escape(&skill.name))
+                        }
+                        None => writeln!(html, "<li>{}</li>", escape(&skill.name)),
+                    }
+                    .unwrap();
+                }
+                html.push_str("</ul>\n");
+            }
+            SectionBody::Courses => {
+                html.push_str("<ul>\n");
+                for course in &content.courses {
+                    let details = course
+                        .details()
+                        .map(|details| format!(" ({})", escape(&details)))
+                        .unwrap_or_default();
+                    writeln!(
+                        html,
+                        "<li><strong>{}</strong>{details}: {}</li>",
+                        escape(&course.name),
+                        escape(&course.syllabus),
+                    )
+                    .unwrap();
+                }
+                html.push_str("</ul>\n");
+            }
+            SectionBody::Education => {
+                for entry in &content.education {
+                    writeln!(
+                        html,
+                        "<h3>{}</h3>\n<p>{}, {} ({})</p>",
+                        escape(&entry.degree),
+                        escape(&entry.institution),
+                        escape(&entry.location),
+                        escape(&entry.duration()),
+                    )
+                    .unwrap();
+                    if !entry.grades.is_empty() {
+                        html.push_str("<ul>\n");
+                        for grade in &entry.grades {
+                            writeln!(html, "<li>{}</li>", escape(grade)).unwrap();
+                        }
+                        html.push_str("</ul>\n");
+                    }
+                }
+            }
+            SectionBody::Achievements => {
+                html.push_str("<ul>\n");
+                for achievement in &content.achievements {
+                    let title = escape(&achievement.title);
+                    let project = achievement.project.as_deref();
+                    match project.and_then(|id| content.project(id)) {
+                        Some(project) => writeln!(
+                            html,
+                            "<li>{title} ({})</li>",
+                            link(&format!("#{}", project.id), &escape(&project.name))
+                        ),
+                        None => writeln!(html, "<li>{title}</li>"),
+                    }
+                    .unwrap();
+                }
+                html.push_str("</ul>\n");
+            }
+            SectionBody::Projects { categories } => {
+                for project in content.projects_in(categories) {
+                    project_html(html, project, images);
+                }
+            }
+            SectionBody::Contacts => {
+                writeln!(
+                    html,
+                    "<address>{}<br>{}</address>",
+                    link(&contact.email_url(), &escape(&contact.email)),
+                    link(&contact.phone_url(), &escape(&contact.phone)),
+                )
+                .unwrap();
+            }
+        }
+        html.push_str("</section>\n");
+    }
+}
+
+fn project_html(html: &mut String, project: &Project, images: bool) {
+    writeln!(html, "<article id=\"{}\">", escape(&project.id)).unwrap();
+    let name = escape(&project.name);
+    match project.main_link() {
+        Some(url) => writeln!(html, "<h3>{}</h3>", link(url, &name)),
+        None => writeln!(html, "<h3>{name}</h3>"),
+    }
+    .unwrap();
+    let mut details = Vec::new();
+    if let (Some(start), Some(duration)) = (project.start, project.duration()) {
+        details.push(format!(
+            "<time datetime=\"{}\">{}</time>",
+            start.iso(),
+            escape(&duration)
+        ));
+    }
+    details.extend(project.role.as_deref().map(escape));
+    if !project.tech.is_empty() {
+        details.push(escape(&project.tech.join(", ")));
+    }
+    if !details.is_empty() {
+        writeln!(html, "<p>{}</p>", details.join(" · ")).unwrap();
+    }
+
+    let parser = pulldown_cmark::Parser::new_ext(
+        &project.description,
+        pulldown_cmark::Options::ENABLE_STRIKETHROUGH,
+    );
+    pulldown_cmark::html::push_html(html, parser);
+
+    // Printing leaves the images out.
+    if images {
+        for image in &project.images {
+            let Some(asset) = ImageAsset::find(&image.path) else {
+                continue;
+            };
+            let srcset: Vec<String> = asset
+                .variants
+                .iter()
+                .map(|variant| format!("projects/{} {}w", variant.file, variant.width))
+                .collect();
+            writeln!(
+                html,
+                "<figure><img src=\"{}\" srcset=\"{}\" sizes=\"(max-width: 52em) 100vw, 50em\" width=\"{}\" height=\"{}\" alt=\"{}\" loading=\"lazy\">{}</figure>",
+                escape(&published_path(asset)),
+                srcset.join(", "),
+                asset.size[0],
+                asset.size[1],
+                escape(image.caption.as_deref().unwrap_or(&project.name)),
+                image
+                    .caption
+                    .as_ref()
+                    .map(|caption| format!("<figcaption>{}</figcaption>", escape(caption)))
+                    .unwrap_or_default(),
+            )
+            .unwrap();
+        }
+    }
+
+    let links = [
+        ("Source", &project.links.source),
+        ("Demo", &project.links.demo),
+        ("Paper", &project.links.paper),
+    ];
+    let links: Vec<String> = links
+        .into_iter()
+        .filter_map(|(text, url)| Some(link(url.as_deref()?, text)))
+        .collect();
+    if !links.is_empty() {
+        writeln!(html, "<p>{}</p>", links.join(" · ")).unwrap();
+    }
+    html.push_str("</article>\n");
+}
+
+/// Path of the image in its original size on the site. build.rs only
+/// publishes the generated copies, the last of which has the original size.
+fn published_path(asset: &ImageAsset) -> String {
+    let file = asset.variants.last().map_or("", |variant| variant.file);
+    format!("projects/{file}")
+}
+
+/// The generated project images, as file names in `projects/` and contents.
+/// Native builds embed them; the web build fetches them from next to the page.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn project_images() -> impl Iterator<Item = (&'static str, &'static [u8])> {
+    crate::images::PROJECT_IMAGES
+        .iter()
+        .flat_map(|asset| asset.variants)
+        .map(|variant| (variant.file, variant.bytes))
+}
+
+/// A link around already escaped HTML.
+fn link(url: &str, html: &str) -> String {
+    format!("<a href=\"{}\">{html}</a>", escape(url))
+}
+
+/// The text with the characters that mean something in HTML escaped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
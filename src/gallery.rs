@@ -0,0 +1,161 @@
+use crate::app::{add_text, TextType};
+use crate::content::ProjectImage;
+use crate::images::ImageAsset;
+
+const MAX_ZOOM: f32 = 4.0;
+/// How far an image has to be dragged to move to the next one.
+const SWIPE_DISTANCE: f32 = 50.0;
+
+/// An image carousel with captions, zoom and pan.
+///
+/// Arrow keys and horizontal swipes move between images. Clicking zooms in
+/// on the clicked point (or back out), dragging a zoomed image pans it, and
+/// pinching or ctrl-scrolling zooms gradually.
+pub struct Gallery {
+    index: usize,
+    /// 1.0 shows the whole image.
+    zoom: f32,
+    /// Centre of the visible part of the image, in uv coordinates.
+    center: egui::Pos2,
+    /// Horizontal distance of the swipe in progress.
+    swipe: f32,
+}
+
+impl Default for Gallery {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            zoom: 1.0,
+            center: egui::pos2(0.5, 0.5),
+            swipe: 0.0,
+        }
+    }
+}
+
+impl Gallery {
+    pub fn ui(&mut self, ui: &mut egui::Ui, images: &[ProjectImage]) {
+        if images.is_empty() {
+            return;
+        }
+        self.index = self.index.min(images.len() - 1);
+
+        let mut step = 0;
+        if images.len() > 1 {
+            ui.horizontal(|ui| {
+                if ui.button(add_text(TextType::Paragraph, "◀")).clicked() {
+                    step = -1;
+                }
+                ui.label(add_text(
+                    TextType::Paragraph,
+                    &format!("{} / {}", self.index + 1, images.len()),
+                ));
+                if ui.button(add_text(TextType::Paragraph, "▶")).clicked() {
+                    step = 1;
+                }
+            });
+            // Leave the arrow keys to text fields that have focus.
+            if ui.memory(|memory| memory.focus().is_none()) {
+                ui.input(|input| {
+                    if input.key_pressed(egui::Key::ArrowLeft) {
+                        step = -1;
+                    } else if input.key_pressed(egui::Key::ArrowRight) {
+                        step = 1;
+                    }
+                });
+            }
+        }
+
+        let image = &images[self.index];
+        let width = ui.available_width();
+        let source = ImageAsset::find(&image.path)
+            .and_then(|asset| asset.source(ui.ctx(), width * self.zoom));
+        if let Some(source) = source {
+            let uv = egui::Rect::from_center_size(self.center, egui::Vec2::splat(1.0 / self.zoom));
+            let response = ui
+                .vertical_centered(|ui| {
+                    ui.add(
+                        egui::Image::new(source)
+                            .fit_to_exact_size(egui::vec2(width, 500.0))
+                            .maintain_aspect_ratio(true)
+                            .uv(uv)
+                            .sense(egui::Sense::click_and_drag()),
+                    )
+                })
+                .inner;
+            step += self.interact(ui, &response, uv, images.len());
+        }
+        if let Some(caption) = &image.caption {
+            ui.vertical_centered(|ui| {
+                ui.label(add_text(TextType::Paragraph, caption).weak());
+            });
+        }
+
+        if step != 0 {
+            let count = images.len() as isize;
+            self.show((self.index as isize + step).rem_euclid(count) as usize);
+        }
+    }
+
+    /// Handles zooming, panning and swiping on the image. Returns the number of images to move by.
+    fn interact(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        uv: egui::Rect,
+        count: usize,
+    ) -> isize {
+        let rect = response.rect;
+        let to_uv = |pos: egui::Pos2| uv.min + (pos - rect.min) / rect.size() * uv.size();
+
+        if response.clicked() {
+            if self.zoom > 1.0 {
+                self.zoom = 1.0;
+            } else if let Some(pos) = response.interact_pointer_pos() {
+                self.zoom = 2.0;
+                self.center = to_uv(pos);
+            }
+        }
+        if response.hovered() {
+            let zoom_delta = ui.input(|input| input.zoom_delta());
+            if zoom_delta != 1.0 {
+                self.zoom = (self.zoom * zoom_delta).clamp(1.0, MAX_ZOOM);
+            }
+        }
+
+        let mut step = 0;
+        if response.dragged() {
+            if self.zoom > 1.0 {
+                self.center -= response.drag_delta() / rect.size() * uv.size();
+            } else {
+                self.swipe += response.drag_delta().x;
+            }
+        }
+        if response.drag_released() {
+            if count > 1 && self.swipe.abs() > SWIPE_DISTANCE {
+                // Swiping left brings in the next image from the right.
+                step = if self.swipe < 0.0 { 1 } else { -1 };
+            }
+            self.swipe = 0.0;
+        }
+
+        // Keep the visible part inside the image.
+        let half = 0.5 / self.zoom;
+        self.center.x = self.center.x.clamp(half, 1.0 - half);
+        self.center.y = self.center.y.clamp(half, 1.0 - half);
+
+        let cursor = if self.zoom > 1.0 {
+            egui::CursorIcon::Grab
+        } else {
+            egui::CursorIcon::ZoomIn
+        };
+        response.clone().on_hover_cursor(cursor);
+        step
+    }
+
+    fn show(&mut self, index: usize) {
+        *self = Self {
+            index,
+            ..Self::default()
+        };
+    }
+}
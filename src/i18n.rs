@@ -0,0 +1,181 @@
+//! Translations of the user interface.
+//!
+//! Messages live in one catalog per locale, `assets/i18n/<code>.json`, mapping
+//! keys to text with `{name}` placeholders. A key missing from a catalog falls
+//! back to English, so a translation can be incomplete.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Locale {
+    English,
+    German,
+    Hindi,
+}
+
+impl Locale {
+    /// Every locale there is a catalog for, in the order of their `repr`.
+    const CATALOGUED: [Self; 3] = [Self::English, Self::German, Self::Hindi];
+
+    /// The locales that can be shown: Hindi needs the Devanagari font, which
+    /// builds without `assets/fonts/NotoSansDevanagari-Regular.ttf` lack.
+    pub const ALL: &'static [Self] = if DEVANAGARI_FONT.is_some() {
+        &Self::CATALOGUED
+    } else {
+        &[Self::English, Self::German]
+    };
+
+    /// The ISO 639-1 code, which is also the catalog's file name.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Hindi => "hi",
+        }
+    }
+
+    /// The name of the language in the language itself.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::German => "Deutsch",
+            Self::Hindi => "हिन्दी",
+        }
+    }
+
+    /// The locale for a BCP 47 language tag such as "de-AT", if there is one.
+    pub fn from_language_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|locale| locale.code() == language)
+    }
+
+    /// The first of the user's preferred languages that there is a catalog for.
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let preferred = crate::web::browser_languages();
+        #[cfg(not(target_arch = "wasm32"))]
+        let preferred: Vec<String> = Vec::new();
+
+        preferred
+            .iter()
+            .find_map(|tag| Self::from_language_tag(tag))
+            .unwrap_or(Self::English)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/fonts.rs"));
+
+/// Adds the fonts the translations need to egui's defaults.
+pub fn install_fonts(ctx: &egui::Context) {
+    let Some(devanagari) = DEVANAGARI_FONT else {
+        log::warn!("Built without assets/fonts/NotoSansDevanagari-Regular.ttf; Hindi is not offered");
+        return;
+    };
+    let mut fonts = egui::FontDefinitions::default();
+    fonts.font_data.insert(
+        "devanagari".to_owned(),
+        egui::FontData::from_static(devanagari),
+    );
+    for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+        fonts
+            .families
+            .entry(family)
+            .or_default()
+            .push("devanagari".to_owned());
+    }
+    ctx.set_fonts(fonts);
+}
+
+type Catalog = HashMap<String, String>;
+
+lazy_static::lazy_static! {
+    static ref CATALOGS: [Catalog; 3] = [
+        parse(Locale::English, include_str!("../assets/i18n/en.json")),
+        parse(Locale::German, include_str!("../assets/i18n/de.json")),
+        parse(Locale::Hindi, include_str!("../assets/i18n/hi.json")),
+    ];
+}
+
+fn parse(locale: Locale, json: &str) -> Catalog {
+    serde_json::from_str(json)
+        .unwrap_or_else(|err| panic!("assets/i18n/{}.json is invalid: {err}", locale.code()))
+}
+
+/// The locale messages are shown in. Set at startup and whenever the user picks another.
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::English as u8);
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    Locale::CATALOGUED[usize::from(LOCALE.load(Ordering::Relaxed))]
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    CATALOGS[locale as usize].get(key).map(String::as_str)
+}
+
+/// The message for the key in the current locale.
+pub fn tr(key: &'static str) -> &'static str {
+    tr_or(key, key)
+}
+
+/// Like [`tr`], for keys that are not in every catalog, e.g. ones derived from content ids.
+pub fn tr_or<'a>(key: &str, default: &'a str) -> &'a str {
+    lookup(locale(), key)
+        .or_else(|| lookup(Locale::English, key))
+        .unwrap_or(default)
+}
+
+/// The message for the key with its `{name}` placeholders filled in.
+pub fn tr_with(key: &'static str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(tr(key).to_owned(), |message, (name, value)| {
+            message.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offers_hindi_with_the_devanagari_font() {
+        let font = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/fonts/NotoSansDevanagari-Regular.ttf"
+        );
+        assert_eq!(
+            DEVANAGARI_FONT.is_some(),
+            std::path::Path::new(font).exists()
+        );
+        assert_eq!(
+            Locale::ALL.contains(&Locale::Hindi),
+            DEVANAGARI_FONT.is_some()
+        );
+        assert_eq!(
+            Locale::from_language_tag("hi-IN"),
+            DEVANAGARI_FONT.map(|_| Locale::Hindi)
+        );
+    }
+
+    #[test]
+    fn matches_language_tags() {
+        assert_eq!(Locale::from_language_tag("de-AT"), Some(Locale::German));
+        assert_eq!(Locale::from_language_tag("EN_us"), Some(Locale::English));
+        assert_eq!(Locale::from_language_tag("fr"), None);
+    }
+
+    #[test]
+    fn catalogs_parse() {
+        for locale in Locale::CATALOGUED {
+            assert!(!CATALOGS[locale as usize].is_empty(), "{}", locale.code());
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Project images, in the sizes generated by `build.rs`: embedded in native
+//! builds, fetched on demand on the web.
+
+use egui::load::TexturePoll;
+
+use crate::content::Content;
+use crate::i18n::tr;
+
+/// A project image, available in several sizes.
+pub struct ImageAsset {
+    /// Path relative to `assets/`, as used in `assets/content.json`.
+    pub path: &'static str,
+    /// Width and height of the original, in pixels.
+    pub size: [u32; 2],
+    /// Smallest first; the last one has the original size.
+    pub variants: &'static [ImageVariant],
+}
+
+pub struct ImageVariant {
+    /// In pixels.
+    pub width: u32,
+    pub file: &'static str,
+    /// Native builds embed the images in the binary.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub bytes: &'static [u8],
+}
+
+include!(concat!(env!("OUT_DIR"), "/project_images.rs"));
+
+/// Warns about images the content refers to that are not in `assets/projects`.
+/// Done once at startup, as images are looked up on every frame.
+pub fn check_content(content: &Content) {
+    for project in &content.projects {
+        for image in &project.images {
+            if ImageAsset::find(&image.path).is_none() {
+                log::warn!("No project image {:?} in assets/projects", image.path);
+            }
+        }
+    }
+}
+
+impl ImageAsset {
+    pub fn find(path: &str) -> Option<&'static Self> {
+        PROJECT_IMAGES.iter().find(|asset| asset.path == path)
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.size[0] as f32 / self.size[1] as f32
+    }
+
+    /// The smallest variant that is at least the given width in pixels, or the largest one.
+    fn variant_for(&self, width: f32) -> &'static ImageVariant {
+        self.variants
+            .iter()
+            .find(|variant| variant.width as f32 >= width)
+            .or(self.variants.last())
+            .expect("every image has its original size")
+    }
+
+    /// The image at a resolution fit for showing it `width` points wide.
+    pub fn source(&self, ctx: &egui::Context, width: f32) -> Option<egui::ImageSource<'static>> {
+        let variant = self.variant_for(width * ctx.pixels_per_point());
+        variant.source()
+    }
+}
+
+impl ImageVariant {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn source(&self) -> Option<egui::ImageSource<'static>> {
+        Some(egui::ImageSource::Bytes {
+            uri: format!("bytes://projects/{}", self.file).into(),
+            bytes: egui::load::Bytes::Static(self.bytes),
+        })
+    }
+
+    /// The web build serves the images as separate files next to the wasm binary
+    /// (written by `cargo run -- export`), which the http image loader fetches with `ehttp`.
+    #[cfg(target_arch = "wasm32")]
+    fn source(&self) -> Option<egui::ImageSource<'static>> {
+        let url = crate::web::asset_url(&format!("projects/{}", self.file));
+        if url.is_none() {
+            log::warn!("Cannot resolve the URL of {:?}", self.file);
+        }
+        Some(egui::ImageSource::Uri(url?.into()))
+    }
+}
+
+/// Shows a project image fitted into a box of at most the given size.
+///
+/// The space is reserved from the image's known size, but the image is only
+/// loaded once it is scrolled into view, with a spinner standing in while it
+/// loads and a message if it fails to.
+pub fn lazy_image(
+    ui: &mut egui::Ui,
+    path: &str,
+    max_size: egui::Vec2,
+    sense: egui::Sense,
+) -> egui::Response {
+    let Some(asset) = ImageAsset::find(path) else {
+        let (rect, response) = ui.allocate_exact_size(max_size, sense);
+        paint_error(ui, rect, tr("image.not_found"));
+        return response;
+    };
+    let size = fit(asset.aspect_ratio(), max_size);
+    let (rect, response) = ui.allocate_exact_size(size, sense);
+    if !ui.is_rect_visible(rect) {
+        return response;
+    }
+    let Some(source) = asset.source(ui.ctx(), size.x) else {
+        paint_error(ui, rect, tr("image.load_failed"));
+        return response;
+    };
+
+    match egui::Image::new(source).load_for_size(ui.ctx(), size) {
+        Ok(TexturePoll::Ready { texture }) => {
+            egui::Image::from_texture(texture).paint_at(ui, rect);
+            response
+        }
+        Ok(TexturePoll::Pending { .. }) => {
+            ui.painter()
+                .rect_filled(rect, 4.0, ui.visuals().faint_bg_color);
+            let spinner = egui::Rect::from_center_size(rect.center(), egui::Vec2::splat(24.0));
+            egui::Spinner::new().paint_at(ui, spinner);
+            response
+        }
+        Err(err) => {
+            paint_error(ui, rect, tr("image.load_failed"));
+            response.on_hover_text(err.to_string())
+        }
+    }
+}
+
+/// The largest size with the given aspect ratio that fits into `max_size`.
+fn fit(aspect_ratio: f32, max_size: egui::Vec2) -> egui::Vec2 {
+    if max_size.x / max_size.y > aspect_ratio {
+        egui::vec2(max_size.y * aspect_ratio, max_size.y)
+    } else {
+        egui::vec2(max_size.x, max_size.x / aspect_ratio)
+    }
+}
+
+fn paint_error(ui: &egui::Ui, rect: egui::Rect, message: &str) {
+    let visuals = ui.visuals();
+    ui.painter().rect_stroke(
+        rect,
+        4.0,
+        egui::Stroke::new(1.0, visuals.widgets.noninteractive.bg_stroke.color),
+    );
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        message,
+        egui::FontId::proportional(16.0),
+        visuals.error_fg_color,
+    );
+}
@@ -1,4 +1,25 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod apps;
+mod contact;
+mod content;
+mod desktop;
+mod download;
+pub mod export;
+mod gallery;
+mod i18n;
+mod images;
+mod markdown;
+mod pdf;
+mod quotes;
+mod reader;
+pub mod resume;
+mod search;
+mod skills;
+mod time;
+mod timeline;
+mod toc;
+#[cfg(target_arch = "wasm32")]
+mod web;
 pub use app::Website;
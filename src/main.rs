@@ -6,6 +6,19 @@
 fn main() -> eframe::Result<()> {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    // Any arguments are a command to run instead of the app.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        match run_command(&args) {
+            Ok(message) => println!("{message}"),
+            Err(err) => {
+                eprintln!("{err}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+        return Ok(());
+    }
+
     let native_options = eframe::NativeOptions {
         initial_window_size: Some([400.0, 300.0].into()),
         min_window_size: Some([300.0, 220.0].into()),
@@ -19,6 +32,79 @@ fn main() -> eframe::Result<()> {
     )
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+const USAGE: &str = "\
+Usage:
+    adarsh_das                     Open the website as a desktop app
+    adarsh_das resume [OPTIONS]    Render the résumé from assets/content.json as a PDF
+    adarsh_das export <DIR>        Add the content as static HTML to the index.html built
+                                   by Trunk in DIR, and write a sitemap and the project
+                                   images next to it
+
+Options for resume:
+    --template <NAME>    classic, compact or modern [default: classic]
+    --output <PATH>      Where to write the PDF [default: target/resume.pdf]";
+
+/// Runs a command-line command, returning what to tell the user.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_command(args: &[String]) -> Result<String, String> {
+    use adarsh_das::resume::{self, Template};
+
+    match args[0].as_str() {
+        "resume" => {
+            let mut template = Template::default();
+            let mut output = resume::DEFAULT_OUTPUT.to_owned();
+            let mut options = args[1..].iter();
+            while let Some(option) = options.next() {
+                let mut value = || {
+                    options
+                        .next()
+                        .ok_or_else(|| format!("{option} needs a value"))
+                };
+                match option.as_str() {
+                    "--template" => {
+                        let name = value()?;
+                        template = Template::from_name(name)
+                            .ok_or_else(|| format!("There is no template called {name:?}"))?;
+                    }
+                    "--output" => output = value()?.clone(),
+                    _ => return Err(format!("Unknown option {option:?}")),
+                }
+            }
+            std::fs::write(&output, resume::render(template))
+                .map_err(|err| format!("Failed to write {output}: {err}"))?;
+            Ok(format!("Wrote the {} résumé to {output}", template.name()))
+        }
+        "export" => {
+            use adarsh_das::export;
+
+            let [_, dir] = args else {
+                return Err("export needs the directory Trunk built the site in".to_owned());
+            };
+            let dir = std::path::Path::new(dir);
+            let write = |name: &str, contents: String| {
+                std::fs::write(dir.join(name), contents)
+                    .map_err(|err| format!("Failed to write {name}: {err}"))
+            };
+            let index_html = std::fs::read_to_string(dir.join("index.html"))
+                .map_err(|err| format!("Failed to read index.html: {err}"))?;
+            write("index.html", export::static_page(&index_html)?)?;
+            write("sitemap.xml", export::sitemap())?;
+            write("robots.txt", export::robots_txt())?;
+            let projects = dir.join("projects");
+            std::fs::create_dir_all(&projects)
+                .map_err(|err| format!("Failed to create {}: {err}", projects.display()))?;
+            for (file, bytes) in export::project_images() {
+                std::fs::write(projects.join(file), bytes)
+                    .map_err(|err| format!("Failed to write projects/{file}: {err}"))?;
+            }
+            Ok(format!("Exported the static content to {}", dir.display()))
+        }
+        "help" | "--help" | "-h" => Ok(USAGE.to_owned()),
+        command => Err(format!("Unknown command {command:?}")),
+    }
+}
+
 // When compiling to web using trunk:
 #[cfg(target_arch = "wasm32")]
 fn main() {
@@ -38,16 +124,31 @@ fn main() {
         let loading_text = web_sys::window()
             .and_then(|w| w.document())
             .and_then(|d| d.get_element_by_id("loading_text"));
+        // The text version of the page added by `adarsh_das export`, if any.
+        let static_content = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.get_element_by_id("static_content"));
         match start_result {
             Ok(_) => {
                 loading_text.map(|e| e.remove());
+                if let Some(static_content) = static_content {
+                    static_content.remove();
+                }
             }
             Err(e) => {
-                loading_text.map(|e| {
-                    e.set_inner_html(
-                        "<p> The app has crashed. See the developer console for details. </p>",
-                    )
-                });
+                if let Some(static_content) = static_content {
+                    // Fall back to the text version.
+                    if let Some(loading_text) = loading_text {
+                        loading_text.remove();
+                    }
+                    static_content.remove_attribute("hidden").ok();
+                } else {
+                    loading_text.map(|e| {
+                        e.set_inner_html(
+                            "<p> The app has crashed. See the developer console for details. </p>",
+                        )
+                    });
+                }
                 panic!("failed to start eframe: {e:?}");
             }
         }
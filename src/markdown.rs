@@ -0,0 +1,269 @@
+//! CommonMark text, as used for project descriptions.
+
+use egui::text::LayoutJob;
+use egui_extras::syntax_highlighting::{code_view_ui, CodeTheme};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+
+use crate::app::{add_custom_hyperlink, add_text, append_highlighted, text_size, TextType};
+
+/// Shows CommonMark text: paragraphs, headings, lists, emphasis, links and
+/// fenced code blocks with syntax highlighting.
+///
+/// `id_source` tells the code blocks of different texts apart.
+pub(crate) fn markdown_ui(
+    ui: &mut egui::Ui,
+    id_source: impl std::hash::Hash,
+    text: &str,
+    text_type: TextType,
+    search_words: &[String],
+) {
+    ui.push_id(id_source, |ui| {
+        let mut renderer = Renderer {
+            text_type,
+            search_words,
+            inlines: Vec::new(),
+            style: Style::default(),
+            heading: None,
+            link: None,
+            lists: Vec::new(),
+            item_marker: None,
+            code_block: None,
+            code_blocks: 0,
+        };
+        for event in Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH) {
+            renderer.event(ui, event);
+        }
+        renderer.flush(ui);
+    });
+}
+
+/// The text of CommonMark without its markup, for places that cannot show
+/// formatting. Code blocks are left out; blocks are separated by a space.
+pub(crate) fn plain_text(text: &str) -> String {
+    let mut plain = String::new();
+    let mut in_code_block = false;
+    for event in Parser::new_ext(text, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Text(text) | Event::Code(text) if !in_code_block => plain.push_str(&text),
+            Event::SoftBreak
+            | Event::HardBreak
+            | Event::End(Tag::Paragraph | Tag::Item | Tag::Heading(..)) => {
+                if !plain.ends_with(' ') {
+                    plain.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+    plain.trim_end().to_owned()
+}
+
+/// Inline content of the block being collected.
+enum Inline {
+    Text(LayoutJob),
+    Link { text: String, url: String },
+}
+
+#[derive(Default)]
+struct Style {
+    strong: bool,
+    emphasis: bool,
+    strikethrough: bool,
+}
+
+/// Turns the parser events into widgets, one block at a time.
+struct Renderer<'a> {
+    text_type: TextType,
+    search_words: &'a [String],
+    inlines: Vec<Inline>,
+    style: Style,
+    heading: Option<HeadingLevel>,
+    /// Url and text of the link being collected.
+    link: Option<(String, String)>,
+    /// The next number of every open list, `None` for bullet lists.
+    lists: Vec<Option<u64>>,
+    /// "•" or "1." for the list item whose first block is being collected.
+    item_marker: Option<String>,
+    /// Language and code of the fenced code block being collected.
+    code_block: Option<(String, String)>,
+    code_blocks: usize,
+}
+
+impl Renderer<'_> {
+    fn event(&mut self, ui: &mut egui::Ui, event: Event<'_>) {
+        match event {
+            Event::Start(Tag::Heading(level, ..)) => self.heading = Some(level),
+            Event::End(Tag::Heading(..)) => {
+                self.flush(ui);
+                self.heading = None;
+            }
+            Event::End(Tag::Paragraph | Tag::Item) => self.flush(ui),
+            Event::Start(Tag::List(start)) => {
+                // Text of the enclosing item comes before the nested list.
+                self.flush(ui);
+                self.lists.push(start);
+            }
+            Event::End(Tag::List(_)) => {
+                self.flush(ui);
+                self.lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                self.flush(ui);
+                self.item_marker = Some(match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}.", *number - 1)
+                    }
+                    _ => "•".to_owned(),
+                });
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                self.flush(ui);
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_owned(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_block = Some((language, String::new()));
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some((language, code)) = self.code_block.take() {
+                    self.code_block_ui(ui, &language, &code);
+                }
+            }
+            Event::Start(Tag::Emphasis) => self.style.emphasis = true,
+            Event::End(Tag::Emphasis) => self.style.emphasis = false,
+            Event::Start(Tag::Strong) => self.style.strong = true,
+            Event::End(Tag::Strong) => self.style.strong = false,
+            Event::Start(Tag::Strikethrough) => self.style.strikethrough = true,
+            Event::End(Tag::Strikethrough) => self.style.strikethrough = false,
+            Event::Start(Tag::Link(_, url, _)) => {
+                self.link = Some((url.to_string(), String::new()))
+            }
+            Event::End(Tag::Link(..)) => {
+                if let Some((url, text)) = self.link.take() {
+                    self.inlines.push(Inline::Link { text, url });
+                }
+            }
+            Event::Text(text) => match &mut self.code_block {
+                Some((_, code)) => code.push_str(&text),
+                None => self.append(ui, &text, self.format(ui)),
+            },
+            Event::Code(code) => {
+                let format = egui::TextFormat {
+                    font_id: egui::FontId::monospace(text_size(self.text_type) * 0.9),
+                    background: ui.visuals().code_bg_color,
+                    ..self.format(ui)
+                };
+                self.append(ui, &code, format);
+            }
+            Event::SoftBreak => self.append(ui, " ", self.format(ui)),
+            Event::HardBreak => self.append(ui, "\n", self.format(ui)),
+            Event::Rule => {
+                self.flush(ui);
+                ui.separator();
+            }
+            _ => {}
+        }
+    }
+
+    fn format(&self, ui: &egui::Ui) -> egui::TextFormat {
+        let visuals = ui.visuals();
+        let size = match self.heading {
+            Some(HeadingLevel::H1 | HeadingLevel::H2) => text_size(TextType::SubHeading),
+            _ => text_size(self.text_type),
+        };
+        let color = if self.style.strong || self.heading.is_some() {
+            visuals.strong_text_color()
+        } else {
+            visuals.text_color()
+        };
+        egui::TextFormat {
+            font_id: egui::FontId::proportional(size),
+            color,
+            italics: self.style.emphasis,
+            strikethrough: if self.style.strikethrough {
+                egui::Stroke::new(1.0, color)
+            } else {
+                egui::Stroke::NONE
+            },
+            ..Default::default()
+        }
+    }
+
+    fn append(&mut self, ui: &egui::Ui, text: &str, format: egui::TextFormat) {
+        if let Some((_, link_text)) = &mut self.link {
+            link_text.push_str(text);
+            return;
+        }
+        match self.inlines.last_mut() {
+            Some(Inline::Text(job)) => append_highlighted(ui, job, text, format, self.search_words),
+            _ => {
+                let mut job = LayoutJob::default();
+                append_highlighted(ui, &mut job, text, format, self.search_words);
+                self.inlines.push(Inline::Text(job));
+            }
+        }
+    }
+
+    /// Lays out the collected inline content as one wrapped block.
+    fn flush(&mut self, ui: &mut egui::Ui) {
+        if self.inlines.is_empty() {
+            return;
+        }
+        let inlines = std::mem::take(&mut self.inlines);
+        let text_type = self.text_type;
+        let show_inlines = |ui: &mut egui::Ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                for inline in inlines {
+                    match inline {
+                        Inline::Text(job) => {
+                            ui.label(job);
+                        }
+                        Inline::Link { text, url } => {
+                            add_custom_hyperlink(ui, add_text(text_type, &text), &url);
+                        }
+                    }
+                }
+            });
+        };
+
+        if self.lists.is_empty() {
+            show_inlines(ui);
+            return;
+        }
+        // Later blocks of an item line up with the text after the marker.
+        let marker = self.item_marker.take().unwrap_or_default();
+        ui.horizontal_top(|ui| {
+            ui.add_space(20.0 * (self.lists.len() - 1) as f32);
+            let (rect, _) = ui.allocate_exact_size(
+                egui::vec2(text_size(text_type) * 1.5, text_size(text_type)),
+                egui::Sense::hover(),
+            );
+            ui.painter().text(
+                rect.left_top(),
+                egui::Align2::LEFT_TOP,
+                marker,
+                egui::FontId::proportional(text_size(text_type)),
+                ui.visuals().text_color(),
+            );
+            ui.vertical(show_inlines);
+        });
+    }
+
+    fn code_block_ui(&mut self, ui: &mut egui::Ui, language: &str, code: &str) {
+        let theme = CodeTheme::from_memory(ui.ctx());
+        egui::ScrollArea::horizontal()
+            .id_source(self.code_blocks)
+            .show(ui, |ui| {
+                code_view_ui(ui, &theme, code.trim_end(), language);
+            });
+        self.code_blocks += 1;
+    }
+}
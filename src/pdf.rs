@@ -0,0 +1,345 @@
+//! Just enough of PDF to write a text document: pages of text in the standard
+//! Helvetica fonts, lines and links.
+//!
+//! The standard fonts are built into every PDF reader, so nothing is embedded,
+//! but they only cover the Windows-1252 characters. Others are written as "?".
+
+use std::fmt::Write as _;
+
+/// A4 in points, the unit of PDF coordinates.
+pub const A4: [f32; 2] = [595.0, 842.0];
+
+/// An RGB colour with components from 0 to 1.
+pub type Color = [f32; 3];
+
+pub const BLACK: Color = [0.0, 0.0, 0.0];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Font {
+    Regular,
+    Bold,
+    Italic,
+}
+
+impl Font {
+    const ALL: [Self; 3] = [Self::Regular, Self::Bold, Self::Italic];
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Self::Regular => "Helvetica",
+            Self::Bold => "Helvetica-Bold",
+            Self::Italic => "Helvetica-Oblique",
+        }
+    }
+
+    /// The name of the font in the page resources.
+    fn resource(self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+            Self::Italic => "F3",
+        }
+    }
+
+    /// Width of the text in points.
+    pub fn width(self, text: &str, size: f32) -> f32 {
+        let widths = match self {
+            Self::Regular | Self::Italic => &HELVETICA_WIDTHS,
+            Self::Bold => &HELVETICA_BOLD_WIDTHS,
+        };
+        let units: u32 = text
+            .chars()
+            .map(|c| match c {
+                ' '..='~' => u32::from(widths[c as usize - 32]),
+                '•' => 350,
+                '—' => 1000,
+                '‘' | '’' | '‚' => 222,
+                '“' | '”' | '„' => 333,
+                // Roughly the width of a lower case letter, e.g. for "ä" or "é".
+                _ => 556,
+            })
+            .sum();
+        units as f32 * size / 1000.0
+    }
+}
+
+/// Advance widths of the printable ASCII characters, in thousandths of the font size.
+#[rustfmt::skip]
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611,
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556,
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611,
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// One page, drawn on in PDF coordinates: points from the bottom left corner.
+#[derive(Default)]
+pub struct Page {
+    content: String,
+    /// Clickable areas (left, bottom, right, top) and the urls they open.
+    links: Vec<([f32; 4], String)>,
+}
+
+impl Page {
+    /// Writes the text with its baseline starting at `(x, y)`.
+    pub fn text(&mut self, [x, y]: [f32; 2], font: Font, size: f32, color: Color, text: &str) {
+        let [r, g, b] = color;
+        writeln!(
+            self.content,
+            "BT /{} {size:.2} Tf {r:.3} {g:.3} {b:.3} rg {x:.2} {y:.2} Td {} Tj ET",
+            font.resource(),
+            string_literal(&encode(text)),
+        )
+        .unwrap();
+    }
+
+    pub fn line(&mut self, [x1, y1]: [f32; 2], [x2, y2]: [f32; 2], width: f32, color: Color) {
+        let [r, g, b] = color;
+        writeln!(
+            self.content,
+            "{r:.3} {g:.3} {b:.3} RG {width:.2} w {x1:.2} {y1:.2} m {x2:.2} {y2:.2} l S",
+        )
+        .unwrap();
+    }
+
+    /// Makes the rectangle (left, bottom, right, top) open the url when clicked.
+    pub fn link(&mut self, rect: [f32; 4], url: &str) {
+        self.links.push((rect, url.to_owned()));
+    }
+}
+
+/// A document of A4 pages.
+pub struct Document {
+    title: String,
+    author: String,
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn new(title: &str, author: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            author: author.to_owned(),
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn push_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    /// The PDF file. It has no creation date, so the same document gives the same bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Objects 1 to 3 are the catalog, the page tree and the document information,
+        // followed by the fonts and then every page with its contents and links.
+        let fonts_start = 4;
+        let mut next_id = fonts_start + Font::ALL.len();
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_owned(),
+            String::new(),
+            format!(
+                "<< /Title {} /Author {} /Producer (adarsh_das) >>",
+                string_literal(&encode(&self.title)),
+                string_literal(&encode(&self.author)),
+            ),
+        ];
+        for font in Font::ALL {
+            objects.push(format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                font.base_font(),
+            ));
+        }
+        let font_resources: String = Font::ALL
+            .iter()
+            .zip(fonts_start..)
+            .map(|(font, id)| format!("/{} {id} 0 R ", font.resource()))
+            .collect();
+
+        let mut page_ids = Vec::new();
+        for page in &self.pages {
+            let page_id = next_id;
+            let contents_id = page_id + 1;
+            let link_ids = contents_id + 1..contents_id + 1 + page.links.len();
+            next_id = link_ids.end;
+            page_ids.push(page_id);
+
+            let annotations: String = link_ids.clone().map(|id| format!("{id} 0 R ")).collect();
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {font_resources}>> >> /Contents {contents_id} 0 R /Annots [{annotations}] >>",
+                A4[0], A4[1],
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                page.content.len(),
+                page.content,
+            ));
+            for ([left, bottom, right, top], url) in &page.links {
+                objects.push(format!(
+                    "<< /Type /Annot /Subtype /Link /Rect [{left:.2} {bottom:.2} {right:.2} {top:.2}] /Border [0 0 0] /A << /S /URI /URI {} >> >>",
+                    string_literal(url.as_bytes()),
+                ));
+            }
+        }
+        let kids: String = page_ids.iter().map(|id| format!("{id} 0 R ")).collect();
+        objects[1] = format!(
+            "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+            page_ids.len()
+        );
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (id, object) in (1..).zip(&objects) {
+            offsets.push(pdf.len());
+            writeln!(pdf, "{id} 0 obj\n{object}\nendobj").unwrap();
+        }
+        let xref = pdf.len();
+        writeln!(pdf, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1).unwrap();
+        for offset in offsets {
+            writeln!(pdf, "{offset:010} 00000 n ").unwrap();
+        }
+        write!(
+            pdf,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 3 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1,
+        )
+        .unwrap();
+        pdf.into_bytes()
+    }
+}
+
+/// The text in the Windows-1252 encoding of the standard fonts.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// A PDF string, with everything but printable ASCII escaped so the file stays ASCII.
+fn string_literal(bytes: &[u8]) -> String {
+    let mut literal = String::from("(");
+    for &byte in bytes {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                literal.push('\\');
+                literal.push(byte as char);
+            }
+            b' '..=b'~' => literal.push(byte as char),
+            _ => write!(literal, "\\{byte:03o}").unwrap(),
+        }
+    }
+    literal.push(')');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Vec<u8> {
+        let mut document = Document::new("Résumé (draft)", "Adarsh Das");
+        let mut page = Page::default();
+        page.text([50.0, 800.0], Font::Bold, 20.0, BLACK, "Adarsh Das – CV");
+        page.line([50.0, 790.0], [545.0, 790.0], 0.5, BLACK);
+        page.link([50.0, 780.0, 200.0, 790.0], "https://github.com/Saphereye");
+        document.push_page(page);
+        let mut page = Page::default();
+        page.text([50.0, 800.0], Font::Italic, 10.0, BLACK, "Page two");
+        document.push_page(page);
+        document.to_bytes()
+    }
+
+    fn find(haystack: &[u8], needle: &str, from: usize) -> Option<usize> {
+        haystack[from..]
+            .windows(needle.len())
+            .position(|window| window == needle.as_bytes())
+            .map(|position| from + position)
+    }
+
+    #[test]
+    fn xref_offsets_point_at_their_objects() {
+        let pdf = document();
+        let text = String::from_utf8(pdf.clone()).expect("the PDF should be ASCII");
+
+        let startxref = text.rsplit("startxref\n").next().unwrap();
+        let xref: usize = startxref.lines().next().unwrap().parse().unwrap();
+        assert_eq!(find(&pdf, "xref\n", 0), Some(xref));
+
+        let mut lines = text[xref..].lines().skip(1);
+        let count: usize = lines.next().unwrap()[2..].parse().unwrap();
+        // The catalog, pages, info, three fonts, two pages with their contents and a link.
+        assert_eq!(count, 12);
+        assert_eq!(lines.next(), Some("0000000000 65535 f "));
+        for id in 1..count {
+            let entry = lines.next().unwrap();
+            assert!(entry.ends_with(" 00000 n "), "{entry:?}");
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(
+                text[offset..].starts_with(&format!("{id} 0 obj\n")),
+                "object {id} is not at {offset}"
+            );
+        }
+        assert!(text.contains(&format!(
+            "trailer\n<< /Size {count} /Root 1 0 R /Info 3 0 R >>"
+        )));
+    }
+
+    #[test]
+    fn stream_lengths_match_their_contents() {
+        let pdf = document();
+        let mut from = 0;
+        let mut streams = 0;
+        while let Some(start) = find(&pdf, "<< /Length ", from) {
+            let text = std::str::from_utf8(&pdf[start..]).unwrap();
+            let length: usize = text["<< /Length ".len()..text.find(" >>").unwrap()]
+                .parse()
+                .unwrap();
+            let data = find(&pdf, "stream\n", start).unwrap() + "stream\n".len();
+            assert_eq!(find(&pdf, "endstream", data), Some(data + length));
+            from = data;
+            streams += 1;
+        }
+        assert_eq!(streams, 2);
+    }
+
+    #[test]
+    fn writes_windows_1252_strings() {
+        assert_eq!(
+            encode("é – €ß✓"),
+            [0xe9, b' ', 0x96, b' ', 0x80, 0xdf, b'?']
+        );
+        assert_eq!(string_literal(b"a(b)\\c"), r"(a\(b\)\\c)");
+        assert_eq!(string_literal(&[0xe9, b'\n']), r"(\351\012)");
+    }
+
+    #[test]
+    fn is_the_same_every_time() {
+        assert_eq!(document(), document());
+    }
+}
@@ -0,0 +1,24 @@
+//! The quotes shown at the top of the page.
+//!
+//! They live in `assets/quotes.json`, which the loading screen in `index.html`
+//! reads as well, so both show the same quote of the day.
+
+#[derive(serde::Deserialize)]
+pub struct Quote {
+    pub text: String,
+    pub author: String,
+    /// The work the quote is taken from, with its year.
+    pub source: String,
+}
+
+lazy_static::lazy_static! {
+    pub static ref QUOTES: Vec<Quote> = serde_json::from_str(include_str!("../assets/quotes.json"))
+        .expect("assets/quotes.json should be a list of quotes");
+}
+
+/// Index of today's quote in [`QUOTES`]: one after another, a new one every day (UTC).
+///
+/// The loading screen in `index.html` makes the same choice.
+pub fn quote_of_the_day() -> usize {
+    crate::time::unix_day().rem_euclid(QUOTES.len() as i64) as usize
+}
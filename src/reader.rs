@@ -0,0 +1,178 @@
+//! Reader mode: the portfolio as a single column of high-contrast text without
+//! images, for screen readers, printing and small or low-contrast screens.
+
+use crate::app::{add_custom_hyperlink, add_text, TextType};
+use crate::content::{Content, Project, Section, SectionBody, Skill, Status};
+use crate::i18n::tr;
+use crate::markdown;
+
+/// Widest the column of text gets, so lines stay easy to follow.
+const MAX_WIDTH: f32 = 800.0;
+
+/// The frame of the central panel in reader mode: black on white, or white on black.
+pub fn frame(style: &egui::Style) -> egui::Frame {
+    let background = if style.visuals.dark_mode {
+        egui::Color32::BLACK
+    } else {
+        egui::Color32::WHITE
+    };
+    egui::Frame::central_panel(style).fill(background)
+}
+
+pub fn reader_ui(ui: &mut egui::Ui, content: &Content) {
+    let visuals = ui.visuals_mut();
+    let (text, link) = if visuals.dark_mode {
+        (egui::Color32::WHITE, egui::Color32::from_rgb(255, 230, 90))
+    } else {
+        (egui::Color32::BLACK, egui::Color32::from_rgb(0, 0, 170))
+    };
+    visuals.override_text_color = Some(text);
+    visuals.hyperlink_color = link;
+
+    egui::ScrollArea::vertical()
+        .id_source("reader")
+        .show(ui, |ui| {
+            let width = ui.available_width().min(MAX_WIDTH);
+            ui.horizontal(|ui| {
+                ui.add_space((ui.available_width() - width) / 2.0);
+                ui.vertical(|ui| {
+                    ui.set_width(width);
+                    ui.heading(add_text(TextType::Heading, &content.profile.name).strong());
+                    for section in content.sections.iter().chain(&content.sidebar) {
+                        section_ui(ui, content, section);
+                    }
+                });
+            });
+        });
+}
+
+fn section_ui(ui: &mut egui::Ui, content: &Content, section: &Section) {
+    ui.add_space(20.0);
+    ui.heading(add_text(TextType::SubHeading, section.localized_title()).strong());
+    ui.add_space(5.0);
+    match &section.body {
+        SectionBody::Text { paragraphs, .. } => {
+            for paragraph in paragraphs {
+                ui.label(add_text(TextType::Paragraph, &paragraph.text()));
+            }
+        }
+        SectionBody::Skills => {
+            for skill in &content.skills {
+                let mut measures = Vec::new();
+                if let Some(proficiency) = skill.proficiency {
+                    let max = Skill::MAX_PROFICIENCY;
+                    measures.push(format!("{}: {proficiency}/{max}", tr("skills.proficiency")));
+                }
+                if let Some(years) = skill.years() {
+                    measures.push(format!("{}: {years}", tr("skills.years")));
+                }
+                let projects = content.project_count(&skill.name);
+                measures.push(format!("{}: {projects}", tr("skills.projects")));
+                ui.label(add_text(
+                    TextType::Paragraph,
+                    &format!("• {} — {}", skill.name, measures.join(", ")),
+                ));
+            }
+        }
+        SectionBody::Courses => {
+            for course in &content.courses {
+                let mut title = course.name.clone();
+                if let Some(details) = course.details() {
+                    title.push_str(&format!(" ({details})"));
+                }
+                if let Some(grade) = &course.grade {
+                    title.push_str(&format!(" — {grade}"));
+                }
+                ui.label(add_text(TextType::Paragraph, &title).strong());
+                ui.label(add_text(TextType::Paragraph, &course.syllabus));
+                ui.add_space(5.0);
+            }
+        }
+        SectionBody::Education => {
+            for entry in &content.education {
+                ui.label(add_text(TextType::Paragraph, &entry.degree).strong());
+                ui.label(add_text(
+                    TextType::Paragraph,
+                    &format!(
+                        "{}, {} ({})",
+                        entry.institution,
+                        entry.location,
+                        entry.duration()
+                    ),
+                ));
+                for grade in &entry.grades {
+                    ui.label(add_text(TextType::Paragraph, &format!("• {grade}")));
+                }
+                ui.add_space(5.0);
+            }
+        }
+        SectionBody::Achievements => {
+            for achievement in &content.achievements {
+                let mut line = format!("• {}", achievement.title);
+                let project = achievement.project.as_deref();
+                if let Some(project) = project.and_then(|id| content.project(id)) {
+                    line.push_str(&format!(" ({})", project.name));
+                }
+                ui.label(add_text(TextType::Paragraph, &line));
+            }
+        }
+        SectionBody::Projects { categories } => {
+            for project in content.projects_in(categories) {
+                project_ui(ui, project);
+            }
+        }
+        SectionBody::Contacts => {
+            let contact = &content.contact;
+            for line in [
+                format!("{}: {}", tr("contact.email"), contact.email),
+                format!("{}: {}", tr("contact.phone"), contact.phone),
+                format!("{}: {}", tr("contact.github"), contact.github),
+                format!("{}: {}", tr("contact.linkedin"), contact.linkedin),
+            ] {
+                ui.label(add_text(TextType::Paragraph, &line));
+            }
+            add_custom_hyperlink(
+                ui,
+                add_text(TextType::Paragraph, tr("contact.resume")),
+                &content.resume_url(),
+            );
+        }
+    }
+}
+
+fn project_ui(ui: &mut egui::Ui, project: &Project) {
+    ui.add_space(10.0);
+    ui.label(add_text(TextType::SubHeading, &project.name).strong());
+
+    let mut details: Vec<String> = project.role.iter().cloned().collect();
+    details.extend(project.duration());
+    match project.status {
+        Status::Completed => {}
+        Status::Ongoing => details.push(tr("project.ongoing").to_owned()),
+        Status::Archived => details.push(tr("project.archived").to_owned()),
+    }
+    if !project.tech.is_empty() {
+        details.push(project.tech.join(", "));
+    }
+    if !details.is_empty() {
+        ui.label(add_text(TextType::Paragraph, &details.join(" · ")).italics());
+    }
+    markdown::markdown_ui(
+        ui,
+        ("reader", &project.id),
+        &project.description,
+        TextType::Paragraph,
+        &[],
+    );
+    ui.horizontal_wrapped(|ui| {
+        for (label, link) in [
+            (tr("project.source"), &project.links.source),
+            (tr("project.demo"), &project.links.demo),
+            (tr("project.paper"), &project.links.paper),
+        ] {
+            if let Some(link) = link {
+                add_custom_hyperlink(ui, add_text(TextType::Paragraph, label).underline(), link);
+            }
+        }
+    });
+}
@@ -0,0 +1,409 @@
+//! The résumé as a printable PDF, rendered from the portfolio content with
+//! `cargo run -- resume` (see `src/main.rs`).
+//!
+//! The site links to the copy a Trunk hook renders into every build (see
+//! `Trunk.toml` and `ContactInfo::resume`), so it says what the site says. It
+//! is not committed, as it depends on the date it was rendered on, through
+//! `Skill::years`.
+
+use crate::content::{Content, SectionBody, CONTENT};
+use crate::markdown;
+use crate::pdf::{Color, Document, Font, Page, A4, BLACK};
+
+/// Where `resume` writes the PDF unless told otherwise, relative to the repository.
+pub const DEFAULT_OUTPUT: &str = "target/resume.pdf";
+
+/// The look of the résumé. All templates show the same content.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Template {
+    /// Black on white, centred header and ruled section headings.
+    #[default]
+    Classic,
+    /// Smaller type and margins, to fit more on a page.
+    Compact,
+    /// Left-aligned header with blue headings.
+    Modern,
+}
+
+impl Template {
+    pub const ALL: [Self; 3] = [Self::Classic, Self::Compact, Self::Modern];
+
+    /// The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Compact => "compact",
+            Self::Modern => "modern",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|template| template.name() == name)
+    }
+
+    fn style(self) -> Style {
+        match self {
+            Self::Classic => Style {
+                margin: 50.0,
+                body_size: 10.0,
+                heading_size: 12.5,
+                name_size: 22.0,
+                accent: BLACK,
+                centered_header: true,
+                heading_rules: true,
+                leading: 1.35,
+            },
+            Self::Compact => Style {
+                margin: 36.0,
+                body_size: 8.5,
+                heading_size: 10.5,
+                name_size: 17.0,
+                accent: BLACK,
+                centered_header: false,
+                heading_rules: true,
+                leading: 1.25,
+            },
+            Self::Modern => Style {
+                margin: 48.0,
+                body_size: 9.5,
+                heading_size: 13.0,
+                name_size: 24.0,
+                accent: [0.11, 0.32, 0.62],
+                centered_header: false,
+                heading_rules: false,
+                leading: 1.4,
+            },
+        }
+    }
+}
+
+struct Style {
+    margin: f32,
+    body_size: f32,
+    heading_size: f32,
+    name_size: f32,
+    /// Colour of the name, the section headings and their rules.
+    accent: Color,
+    centered_header: bool,
+    /// Whether a line runs below every section heading.
+    heading_rules: bool,
+    /// Height of a line as a multiple of the font size.
+    leading: f32,
+}
+
+const GREY: Color = [0.35, 0.35, 0.35];
+
+/// The PDF of the résumé in the given template.
+pub fn render(template: Template) -> Vec<u8> {
+    let content = &*CONTENT;
+    let mut writer = Writer {
+        style: template.style(),
+        document: Document::new(
+            &format!("{} – Résumé", content.profile.name),
+            &content.profile.name,
+        ),
+        page: Page::default(),
+        y: 0.0,
+    };
+    writer.y = writer.style.margin;
+
+    writer.header(content);
+    for section in content.sections.iter().chain(&content.sidebar) {
+        match &section.body {
+            SectionBody::Text { paragraphs, .. } => {
+                writer.heading(&section.title);
+                for paragraph in paragraphs {
+                    writer.paragraph(&paragraph.text(), Font::Regular, BLACK);
+                }
+            }
+            SectionBody::Skills => {
+                writer.heading(&section.title);
+                let skills: Vec<String> = content
+                    .skills
+                    .iter()
+                    .map(|skill| match skill.years() {
+                        Some(years) => format!("{} ({years} years)", skill.name),
+                        None => skill.name.clone(),
+                    })
+                    .collect();
+                writer.paragraph(&skills.join("  •  "), Font::Regular, BLACK);
+            }
+            SectionBody::Courses => {
+                writer.heading(&section.title);
+                let courses: Vec<String> = content
+                    .courses
+                    .iter()
+                    .map(|course| {
+                        let details: Vec<&str> = [&course.code, &course.grade]
+                            .into_iter()
+                            .filter_map(|detail| detail.as_deref())
+                            .collect();
+                        if details.is_empty() {
+                            course.name.clone()
+                        } else {
+                            format!("{} ({})", course.name, details.join(", "))
+                        }
+                    })
+                    .collect();
+                writer.paragraph(&courses.join("  •  "), Font::Regular, BLACK);
+            }
+            SectionBody::Projects { categories } => {
+                writer.heading(&section.title);
+                for project in content.projects_in(categories) {
+                    let title = match &project.role {
+                        Some(role) => format!("{} — {role}", project.name),
+                        None => project.name.clone(),
+                    };
+                    let dates = project.duration().unwrap_or_default();
+                    writer.entry(&title, &dates, project.main_link());
+                    if !project.tech.is_empty() {
+                        writer.paragraph(&project.tech.join(", "), Font::Italic, GREY);
+                    }
+                    writer.paragraph(
+                        &markdown::plain_text(&project.description),
+                        Font::Regular,
+                        BLACK,
+                    );
+                    writer.y += writer.style.body_size * 0.5;
+                }
+            }
+            SectionBody::Education => {
+                writer.heading(&section.title);
+                for entry in &content.education {
+                    let title = format!("{} — {}", entry.degree, entry.institution);
+                    writer.entry(&title, &entry.duration(), None);
+                    writer.paragraph(&entry.location, Font::Italic, GREY);
+                    if !entry.grades.is_empty() {
+                        writer.paragraph(&entry.grades.join("  •  "), Font::Regular, BLACK);
+                    }
+                    writer.y += writer.style.body_size * 0.5;
+                }
+            }
+            SectionBody::Achievements => {
+                writer.heading(&section.title);
+                for achievement in &content.achievements {
+                    let project = achievement.project.as_deref();
+                    let text = match project.and_then(|id| content.project(id)) {
+                        Some(project) => format!("{} ({})", achievement.title, project.name),
+                        None => achievement.title.clone(),
+                    };
+                    writer.paragraph(&text, Font::Regular, BLACK);
+                }
+            }
+            // The header lists the contact details.
+            SectionBody::Contacts => {}
+        }
+    }
+    writer.finish()
+}
+
+/// Lays out text from the top of the page down, starting new pages as needed.
+struct Writer {
+    style: Style,
+    document: Document,
+    page: Page,
+    /// Distance of the next line from the top of the page.
+    y: f32,
+}
+
+impl Writer {
+    fn left(&self) -> f32 {
+        self.style.margin
+    }
+
+    fn width(&self) -> f32 {
+        A4[0] - 2.0 * self.style.margin
+    }
+
+    /// Starts a new page unless the next `height` points fit on this one.
+    fn reserve(&mut self, height: f32) {
+        if self.y + height > A4[1] - self.style.margin {
+            let page = std::mem::take(&mut self.page);
+            self.document.push_page(page);
+            self.y = self.style.margin;
+        }
+    }
+
+    /// Moves down by a line of text of the given size and returns its baseline, in PDF coordinates.
+    fn next_line(&mut self, size: f32) -> f32 {
+        let height = size * self.style.leading;
+        self.reserve(height);
+        let baseline = self.y + (height + size) / 2.0 - size * 0.2;
+        self.y += height;
+        A4[1] - baseline
+    }
+
+    /// Writes the text at `x` on the baseline, linking it to `url` if there is one.
+    fn text_at(
+        &mut self,
+        x: f32,
+        baseline: f32,
+        (font, size, color): (Font, f32, Color),
+        text: &str,
+        url: Option<&str>,
+    ) {
+        self.page.text([x, baseline], font, size, color, text);
+        if let Some(url) = url {
+            let width = font.width(text, size);
+            self.page
+                .link([x, baseline - size * 0.25, x + width, baseline + size], url);
+        }
+    }
+
+    /// The name, the headline and a line of contact details, each linked.
+    fn header(&mut self, content: &Content) {
+        let style = &self.style;
+        let (name_size, body_size, accent) = (style.name_size, style.body_size, style.accent);
+        let centered = style.centered_header;
+        let x_for = |writer: &Self, width: f32| {
+            if centered {
+                writer.left() + (writer.width() - width) / 2.0
+            } else {
+                writer.left()
+            }
+        };
+
+        let name = &content.profile.name;
+        let baseline = self.next_line(name_size);
+        let x = x_for(self, Font::Bold.width(name, name_size));
+        self.text_at(x, baseline, (Font::Bold, name_size, accent), name, None);
+
+        let headline = &content.profile.headline;
+        let headline_size = body_size * 1.2;
+        let baseline = self.next_line(headline_size);
+        let x = x_for(self, Font::Regular.width(headline, headline_size));
+        self.text_at(
+            x,
+            baseline,
+            (Font::Regular, headline_size, BLACK),
+            headline,
+            None,
+        );
+
+        let contact = &content.contact;
+        let items = [
+            (contact.email.clone(), Some(contact.email_url())),
+            (contact.phone.clone(), Some(contact.phone_url())),
+            (content.profile.location.clone(), None),
+            (display_url(&contact.github), Some(contact.github.clone())),
+            (
+                display_url(&contact.linkedin),
+                Some(contact.linkedin.clone()),
+            ),
+        ];
+        const SEPARATOR: &str = "  |  ";
+        let regular = |text: &str| Font::Regular.width(text, body_size);
+        // As many items on a line as fit.
+        let mut lines: Vec<Vec<&(String, Option<String>)>> = Vec::new();
+        let mut line_width = 0.0;
+        for item in &items {
+            let width = regular(&item.0);
+            match lines.last_mut() {
+                Some(line) if line_width + regular(SEPARATOR) + width <= self.width() => {
+                    line.push(item);
+                    line_width += regular(SEPARATOR) + width;
+                }
+                _ => {
+                    lines.push(vec![item]);
+                    line_width = width;
+                }
+            }
+        }
+        for line in lines {
+            let texts: Vec<&str> = line.iter().map(|(text, _)| text.as_str()).collect();
+            let baseline = self.next_line(body_size);
+            let mut x = x_for(self, regular(&texts.join(SEPARATOR)));
+            for (index, (text, url)) in line.into_iter().enumerate() {
+                let format = (Font::Regular, body_size, GREY);
+                if index > 0 {
+                    self.text_at(x, baseline, format, SEPARATOR, None);
+                    x += regular(SEPARATOR);
+                }
+                self.text_at(x, baseline, format, text, url.as_deref());
+                x += regular(text);
+            }
+        }
+    }
+
+    fn heading(&mut self, title: &str) {
+        let (size, accent) = (self.style.heading_size, self.style.accent);
+        // Keep the heading together with the first lines below it.
+        self.reserve(size * 3.0 + self.style.body_size * self.style.leading * 2.0);
+        self.y += size * 0.6;
+        let baseline = self.next_line(size);
+        let left = self.left();
+        self.text_at(left, baseline, (Font::Bold, size, accent), title, None);
+        if self.style.heading_rules {
+            let rule_y = baseline - size * 0.35;
+            self.page
+                .line([left, rule_y], [left + self.width(), rule_y], 0.6, accent);
+            self.y += size * 0.2;
+        }
+    }
+
+    /// Wrapped text across the full width.
+    fn paragraph(&mut self, text: &str, font: Font, color: Color) {
+        let size = self.style.body_size;
+        for line in wrap(text, font, size, self.width()) {
+            let baseline = self.next_line(size);
+            let left = self.left();
+            self.text_at(left, baseline, (font, size, color), &line, None);
+        }
+    }
+
+    /// A bold title, linked to `url`, with `dates` aligned right on its first line.
+    fn entry(&mut self, title: &str, dates: &str, url: Option<&str>) {
+        let size = self.style.body_size;
+        let dates_width = Font::Regular.width(dates, size);
+        let title_width = self.width() - dates_width - size * 2.0;
+        for (index, line) in wrap(title, Font::Bold, size, title_width)
+            .iter()
+            .enumerate()
+        {
+            let baseline = self.next_line(size);
+            let left = self.left();
+            self.text_at(left, baseline, (Font::Bold, size, BLACK), line, url);
+            if index == 0 {
+                let x = left + self.width() - dates_width;
+                self.text_at(x, baseline, (Font::Regular, size, GREY), dates, None);
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.document.push_page(self.page);
+        self.document.to_bytes()
+    }
+}
+
+/// Splits the text into lines no wider than `width`, breaking between words.
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() {
+            word.to_owned()
+        } else {
+            format!("{line} {word}")
+        };
+        if font.width(&candidate, size) <= width || line.is_empty() {
+            line = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut line, word.to_owned()));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// A url without its scheme, "www." and trailing slash, e.g. "github.com/Saphereye".
+fn display_url(url: &str) -> String {
+    let url = url.split_once("://").map_or(url, |(_, rest)| rest);
+    url.trim_start_matches("www.")
+        .trim_end_matches('/')
+        .to_owned()
+}
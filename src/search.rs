@@ -0,0 +1,328 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::content::{Content, SectionBody};
+
+/// Where a search hit takes the user.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    Section(String),
+    Project(String),
+    /// The course id; the course lives in the section with the given id.
+    Course {
+        section: String,
+        course: String,
+    },
+    /// A skill chip, shown in the section with the given id.
+    Skill {
+        section: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct Document {
+    pub target: Target,
+    /// Message key of what kind of content this is, e.g. "Project".
+    pub kind: &'static str,
+    pub title: String,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Field {
+    Title,
+    Body,
+}
+
+impl Field {
+    fn weight(self) -> f32 {
+        match self {
+            Self::Title => 3.0,
+            Self::Body => 1.0,
+        }
+    }
+}
+
+/// An inverted index over all portfolio content.
+///
+/// Built once at startup, so searching only walks the terms that start with
+/// the query words instead of scanning every string every frame.
+#[derive(Default)]
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    /// Term -> (document index, field, number of occurrences).
+    postings: BTreeMap<String, Vec<(usize, Field, u32)>>,
+}
+
+impl SearchIndex {
+    pub fn new(content: &Content) -> Self {
+        let mut index = Self::default();
+        let section_with = |wanted: fn(&SectionBody) -> bool| {
+            content
+                .sections
+                .iter()
+                .find(|section| wanted(&section.body))
+                .map(|section| section.id.clone())
+                .unwrap_or_default()
+        };
+        let skills_section = section_with(|body| matches!(body, SectionBody::Skills));
+        let courses_section = section_with(|body| matches!(body, SectionBody::Courses));
+
+        for section in &content.sections {
+            let body = match &section.body {
+                SectionBody::Text { paragraphs, .. } => paragraphs
+                    .iter()
+                    .map(|paragraph| paragraph.text())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                SectionBody::Education => content
+                    .education
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "{} {} {} {}",
+                            entry.degree,
+                            entry.institution,
+                            entry.location,
+                            entry.grades.join(" ")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                SectionBody::Achievements => content
+                    .achievements
+                    .iter()
+                    .map(|achievement| achievement.title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => String::new(),
+            };
+            index.add(
+                Document {
+                    target: Target::Section(section.id.clone()),
+                    kind: "search.kind.section",
+                    title: section.title.clone(),
+                },
+                &body,
+            );
+        }
+        for project in &content.projects {
+            let body = format!(
+                "{} {} {}",
+                project.description,
+                project.role.as_deref().unwrap_or_default(),
+                project.tech.join(" ")
+            );
+            index.add(
+                Document {
+                    target: Target::Project(project.id.clone()),
+                    kind: "search.kind.project",
+                    title: project.name.clone(),
+                },
+                &body,
+            );
+        }
+        for skill in &content.skills {
+            index.add(
+                Document {
+                    target: Target::Skill {
+                        section: skills_section.clone(),
+                    },
+                    kind: "search.kind.skill",
+                    title: skill.name.clone(),
+                },
+                "",
+            );
+        }
+        for course in &content.courses {
+            let body = format!(
+                "{} {} {}",
+                course.code.as_deref().unwrap_or_default(),
+                course.semester.as_deref().unwrap_or_default(),
+                course.syllabus
+            );
+            index.add(
+                Document {
+                    target: Target::Course {
+                        section: courses_section.clone(),
+                        course: course.id.clone(),
+                    },
+                    kind: "search.kind.course",
+                    title: course.name.clone(),
+                },
+                &body,
+            );
+        }
+        index
+    }
+
+    fn add(&mut self, document: Document, body: &str) {
+        let id = self.documents.len();
+        for (field, text) in [(Field::Title, document.title.as_str()), (Field::Body, body)] {
+            let mut counts = BTreeMap::<String, u32>::new();
+            for term in terms(text) {
+                *counts.entry(term).or_default() += 1;
+            }
+            for (term, count) in counts {
+                self.postings
+                    .entry(term)
+                    .or_default()
+                    .push((id, field, count));
+            }
+        }
+        self.documents.push(document);
+    }
+
+    pub fn document(&self, id: usize) -> &Document {
+        &self.documents[id]
+    }
+
+    /// Ids of the documents matching every word of the query, best match first.
+    ///
+    /// Query words match any term they are a prefix of, so results show up while typing.
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        let words = terms(query);
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: Vec<Option<f32>> = vec![Some(0.0); self.documents.len()];
+        for word in &words {
+            let mut word_scores = vec![0.0; self.documents.len()];
+            for (term, postings) in self.postings.range(word.clone()..) {
+                if !term.starts_with(word.as_str()) {
+                    break;
+                }
+                // Exact matches rank above prefix matches.
+                let closeness = if term == word { 1.0 } else { 0.5 };
+                for &(id, field, count) in postings {
+                    word_scores[id] += field.weight() * closeness * (1.0 + (count as f32).ln());
+                }
+            }
+            for (score, word_score) in scores.iter_mut().zip(word_scores) {
+                *score = score.filter(|_| word_score > 0.0).map(|s| s + word_score);
+            }
+        }
+
+        let mut hits: Vec<(usize, f32)> = scores
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, score)| Some((id, score?)))
+            .collect();
+        hits.sort_by(|a, b| b.1.total_cmp(&a.1));
+        hits.into_iter().map(|(id, _)| id).collect()
+    }
+}
+
+/// Lowercase words of the text, the unit both the index and the queries work with.
+pub fn terms(text: &str) -> Vec<String> {
+    words(text)
+        .map(|range| text[range].to_lowercase())
+        .collect()
+}
+
+fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = None;
+    text.char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+        .filter_map(move |(index, c)| {
+            if c.is_alphanumeric() {
+                start.get_or_insert(index);
+                None
+            } else {
+                start.take().map(|start| start..index)
+            }
+        })
+}
+
+/// Byte ranges of the text matched by the query words, for highlighting.
+///
+/// Mirrors [`SearchIndex::search`]: the start of every word that has a query word as prefix.
+pub fn match_ranges(text: &str, query_words: &[String]) -> Vec<Range<usize>> {
+    if query_words.is_empty() {
+        return Vec::new();
+    }
+    words(text)
+        .filter_map(|range| {
+            let word = &text[range.clone()];
+            let lowercase = word.to_lowercase();
+            let longest = query_words
+                .iter()
+                .filter(|query| lowercase.starts_with(query.as_str()))
+                .map(|query| query.chars().count())
+                .max()?;
+            let end = word
+                .char_indices()
+                .nth(longest)
+                .map_or(range.end, |(offset, _)| range.start + offset);
+            Some(range.start..end)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(documents: &[(&str, &str)]) -> SearchIndex {
+        let mut index = SearchIndex::default();
+        for (title, body) in documents {
+            index.add(
+                Document {
+                    target: Target::Section(title.to_string()),
+                    kind: "search.kind.section",
+                    title: title.to_string(),
+                },
+                body,
+            );
+        }
+        index
+    }
+
+    #[test]
+    fn matches_every_query_word() {
+        let index = build(&[
+            ("Raytracer", "A renderer written in Rust"),
+            ("Chess engine", "Written in C++"),
+            ("Portfolio", "This site, in Rust with egui"),
+        ]);
+        assert_eq!(index.search("rust"), [0, 2]);
+        assert_eq!(index.search("rust egui"), [2]);
+        assert!(index.search("rust chess").is_empty());
+        assert!(index.search("  ").is_empty());
+    }
+
+    #[test]
+    fn matches_prefixes_while_typing() {
+        let index = build(&[("Raytracer", "Renders spheres"), ("Chess", "Plays chess")]);
+        assert_eq!(index.search("ray"), [0]);
+        assert_eq!(index.search("RENDER"), [0]);
+        assert!(index.search("rayz").is_empty());
+    }
+
+    #[test]
+    fn ranks_titles_and_exact_matches_first() {
+        let index = build(&[("Notes", "About rust"), ("Rust", "Notes")]);
+        assert_eq!(index.search("rust"), [1, 0]);
+
+        let index = build(&[("Rustic", ""), ("Rust", "")]);
+        assert_eq!(index.search("rust"), [1, 0]);
+    }
+
+    #[test]
+    fn highlights_matched_prefixes() {
+        let words = terms("ray c");
+        assert_eq!(
+            match_ranges("Raytracer in C++, by Ray", &words),
+            [0..3, 13..14, 21..24]
+        );
+        assert!(match_ranges("Raytracer", &[]).is_empty());
+    }
+
+    #[test]
+    fn highlights_by_characters_not_bytes() {
+        // "É" is two bytes but one character, as is its lowercase.
+        let words = terms("éc");
+        assert_eq!(match_ranges("École", &words), [0..3]);
+        assert_eq!(match_ranges("नमस्ते दुनिया", &terms("नम")), [0..6]);
+    }
+}
@@ -0,0 +1,217 @@
+//! Charts of the skills listed in the "Strengths" section.
+
+use std::f64::consts::TAU;
+
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoint, PlotPoints, Polygon, Text};
+
+use crate::app::{add_text, TextType};
+use crate::content::{Content, Skill};
+use crate::i18n::{tr, tr_with};
+
+/// What the charts measure each skill by.
+#[derive(Clone, Copy)]
+enum Measure {
+    Proficiency,
+    Years,
+    Projects,
+}
+
+impl Measure {
+    const ALL: [Self; 3] = [Self::Proficiency, Self::Years, Self::Projects];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Proficiency => tr("skills.proficiency"),
+            Self::Years => tr("skills.years"),
+            Self::Projects => tr("skills.projects"),
+        }
+    }
+
+    fn value(self, content: &Content, skill: &Skill) -> Option<f64> {
+        match self {
+            Self::Proficiency => skill.proficiency.map(f64::from),
+            Self::Years => skill.years().map(f64::from),
+            Self::Projects => Some(content.project_count(&skill.name) as f64),
+        }
+    }
+
+    /// How many skills the measure is known for.
+    fn recorded(self, content: &Content) -> usize {
+        content
+            .skills
+            .iter()
+            .filter(|skill| self.value(content, skill).is_some())
+            .count()
+    }
+
+    /// The measures known for every skill, which are the ones charted.
+    fn charted(content: &Content) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|measure| measure.recorded(content) == content.skills.len())
+            .collect()
+    }
+
+    /// The value that reaches the rim of the radar chart.
+    fn max(self, content: &Content) -> f64 {
+        match self {
+            Self::Proficiency => f64::from(Skill::MAX_PROFICIENCY),
+            _ => content
+                .skills
+                .iter()
+                .filter_map(|skill| self.value(content, skill))
+                .fold(1.0, f64::max),
+        }
+    }
+}
+
+/// A bar chart and a radar chart of every skill's proficiency, years of use
+/// and number of projects, as far as they are recorded; side by side if there is room.
+/// Below them, which measures are left out for not being recorded for every skill.
+pub fn skill_charts_ui(ui: &mut egui::Ui, content: &Content) {
+    if content.skills.is_empty() {
+        return;
+    }
+    if ui.available_width() > 800.0 {
+        ui.columns(2, |columns| {
+            bar_chart_ui(&mut columns[0], content);
+            radar_chart_ui(&mut columns[1], content);
+        });
+    } else {
+        bar_chart_ui(ui, content);
+        radar_chart_ui(ui, content);
+    }
+
+    let total = content.skills.len();
+    for measure in Measure::ALL {
+        let recorded = measure.recorded(content);
+        if recorded < total {
+            let note = tr_with(
+                "skills.not_recorded",
+                &[
+                    ("measure", measure.name()),
+                    ("recorded", &recorded.to_string()),
+                    ("total", &total.to_string()),
+                ],
+            );
+            ui.label(add_text(TextType::Paragraph, &note).weak());
+        }
+    }
+}
+
+const BAR_WIDTH: f64 = 0.25;
+
+fn bar_chart_ui(ui: &mut egui::Ui, content: &Content) {
+    let names: Vec<String> = content
+        .skills
+        .iter()
+        .map(|skill| skill.name.clone())
+        .collect();
+    let plot = Plot::new("skill_bars")
+        .legend(Legend::default())
+        .height(300.0)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .show_grid([false, true])
+        .x_axis_formatter(move |x, _, _| {
+            // Only label the ticks at the skills themselves.
+            let index = x.round();
+            if (x - index).abs() > 1e-6 || index < 0.0 {
+                return String::new();
+            }
+            names.get(index as usize).cloned().unwrap_or_default()
+        })
+        .label_formatter(|_, _| String::new());
+
+    let measures = Measure::charted(content);
+    // The bars of a skill are centred on its tick.
+    let first_offset = -(measures.len() as f64 - 1.0) / 2.0;
+    plot.show(ui, |plot_ui| {
+        for (index, measure) in measures.into_iter().enumerate() {
+            let offset = first_offset + index as f64;
+            let bars = content
+                .skills
+                .iter()
+                .enumerate()
+                .filter_map(|(index, skill)| Some((index, skill, measure.value(content, skill)?)))
+                .map(|(index, skill, value)| {
+                    let x = index as f64 + offset * BAR_WIDTH;
+                    Bar::new(x, value).width(BAR_WIDTH).name(&skill.name)
+                })
+                .collect();
+            let name = measure.name();
+            plot_ui.bar_chart(BarChart::new(bars).name(name).element_formatter(Box::new(
+                move |bar, _| format!("{}\n{name}: {}", bar.name, bar.value),
+            )));
+        }
+    });
+}
+
+fn radar_chart_ui(ui: &mut egui::Ui, content: &Content) {
+    let count = content.skills.len();
+    // The first skill points straight up, the rest follow clockwise.
+    let direction = |index: usize| {
+        let angle = TAU / 4.0 - TAU * index as f64 / count as f64;
+        (angle.cos(), angle.sin())
+    };
+    let point = |index: usize, radius: f64| {
+        let (x, y) = direction(index);
+        [x * radius, y * radius]
+    };
+    let grid_color = ui.visuals().widgets.noninteractive.bg_stroke.color;
+
+    let plot = Plot::new("skill_radar")
+        .legend(Legend::default())
+        .height(300.0)
+        .data_aspect(1.0)
+        .show_axes([false, false])
+        .show_grid([false, false])
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .allow_boxed_zoom(false)
+        .include_x(-1.4)
+        .include_x(1.4)
+        .include_y(-1.2)
+        .include_y(1.2)
+        .label_formatter(|_, _| String::new());
+
+    plot.show(ui, |plot_ui| {
+        for ring in [0.25, 0.5, 0.75, 1.0] {
+            let points: PlotPoints = (0..=count)
+                .map(|index| point(index % count, ring))
+                .collect();
+            plot_ui.line(Line::new(points).color(grid_color));
+        }
+        for (index, skill) in content.skills.iter().enumerate() {
+            let spoke: PlotPoints = vec![[0.0, 0.0], point(index, 1.0)].into();
+            plot_ui.line(Line::new(spoke).color(grid_color));
+
+            let (x, y) = direction(index);
+            let anchor = match (x, y) {
+                (x, _) if x > 0.1 => egui::Align2::LEFT_CENTER,
+                (x, _) if x < -0.1 => egui::Align2::RIGHT_CENTER,
+                (_, y) if y > 0.0 => egui::Align2::CENTER_BOTTOM,
+                _ => egui::Align2::CENTER_TOP,
+            };
+            plot_ui.text(
+                Text::new(PlotPoint::new(x * 1.05, y * 1.05), skill.name.as_str()).anchor(anchor),
+            );
+        }
+
+        for measure in Measure::charted(content) {
+            let max = measure.max(content);
+            let points: PlotPoints = content
+                .skills
+                .iter()
+                .enumerate()
+                .filter_map(|(index, skill)| {
+                    Some(point(index, measure.value(content, skill)? / max))
+                })
+                .collect();
+            plot_ui.polygon(Polygon::new(points).name(measure.name()));
+        }
+    });
+}
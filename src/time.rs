@@ -0,0 +1,43 @@
+//! The current date, on native and on the web.
+
+/// Seconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_time() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64())
+}
+
+/// Seconds since the Unix epoch.
+///
+/// `std::time::SystemTime` panics on `wasm32-unknown-unknown`, so this asks JavaScript.
+#[cfg(target_arch = "wasm32")]
+pub fn unix_time() -> f64 {
+    js_sys::Date::now() / 1000.0
+}
+
+/// Days since the Unix epoch, in UTC.
+pub fn unix_day() -> i64 {
+    (unix_time() / 86_400.0).floor() as i64
+}
+
+/// The `(year, month, day)` of a day since the Unix epoch, in the proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,227 @@
+use egui_plot::{Bar, BarChart, Legend, Plot, PlotPoint, Text, VLine};
+
+use crate::content::{Category, Content, MilestoneKind, Status, YearMonth};
+use crate::i18n::tr;
+
+/// The rows of the timeline, top to bottom. Items of a lane share a color.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Lane {
+    Education,
+    Experience,
+    Research,
+    Work,
+    Hobby,
+    Articles,
+}
+
+impl Lane {
+    const ALL: [Self; 6] = [
+        Self::Education,
+        Self::Experience,
+        Self::Research,
+        Self::Work,
+        Self::Hobby,
+        Self::Articles,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Education => tr("timeline.education"),
+            Self::Experience => tr("timeline.experience"),
+            Self::Research => tr("timeline.research"),
+            Self::Work => tr("timeline.work"),
+            Self::Hobby => tr("timeline.hobby"),
+            Self::Articles => tr("timeline.articles"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TimelineItem {
+    title: String,
+    /// E.g. "Mar 2023 – May 2023".
+    duration: String,
+    lane: Lane,
+    /// Fractional years.
+    start: f64,
+    end: f64,
+    /// Plot coordinate of the row the item is drawn in.
+    y: f64,
+    /// Section or project id with the details.
+    anchor: String,
+}
+
+/// Education, experience and projects on a zoomable time axis.
+#[derive(Default)]
+pub struct Timeline {
+    items: Vec<TimelineItem>,
+}
+
+const BAR_HEIGHT: f64 = 0.7;
+
+impl Timeline {
+    pub fn new(content: &Content) -> Self {
+        let now = YearMonth::current();
+        // Items end at the end of their last month, or year if that is all that is known.
+        let span = |start: YearMonth, end: Option<YearMonth>| {
+            (start.as_year(), end.unwrap_or(now).end_as_year())
+        };
+
+        let mut items = Vec::new();
+        for milestone in &content.milestones {
+            let (start, end) = span(milestone.start, milestone.end);
+            let duration = match milestone.end {
+                Some(end) => format!("{} – {end}", milestone.start),
+                None => format!("{} – Present", milestone.start),
+            };
+            items.push(TimelineItem {
+                title: milestone.title.clone(),
+                duration,
+                lane: match milestone.kind {
+                    MilestoneKind::Education => Lane::Education,
+                    MilestoneKind::Internship
+                    | MilestoneKind::Competition
+                    | MilestoneKind::Club => Lane::Experience,
+                },
+                start,
+                end,
+                y: 0.0,
+                anchor: milestone.anchor.clone(),
+            });
+        }
+        for project in &content.projects {
+            let (Some(project_start), Some(duration)) = (project.start, project.duration()) else {
+                continue;
+            };
+            // Finished projects without an end date are drawn over just their start date.
+            let end = match (project.end, project.status) {
+                (None, Status::Ongoing) => None,
+                (end, _) => Some(end.unwrap_or(project_start)),
+            };
+            let (start, end) = span(project_start, end);
+            items.push(TimelineItem {
+                title: project.name.clone(),
+                duration,
+                lane: match project.category {
+                    Category::Research => Lane::Research,
+                    Category::Work => Lane::Work,
+                    Category::Hobby => Lane::Hobby,
+                    Category::Article => Lane::Articles,
+                },
+                start,
+                end,
+                y: 0.0,
+                anchor: project.id.clone(),
+            });
+        }
+        items.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        // Lay out the lanes top to bottom, giving overlapping items of a lane their own rows.
+        let mut next_row = 0.0;
+        for lane in Lane::ALL {
+            let mut row_ends: Vec<f64> = Vec::new();
+            for item in items.iter_mut().filter(|item| item.lane == lane) {
+                let row = match row_ends.iter().position(|&end| end <= item.start) {
+                    Some(row) => row,
+                    None => {
+                        row_ends.push(0.0);
+                        row_ends.len() - 1
+                    }
+                };
+                row_ends[row] = item.end;
+                // Plot y grows upwards, so rows go down from 0.
+                item.y = -(next_row + row as f64);
+            }
+            if !row_ends.is_empty() {
+                next_row += row_ends.len() as f64 + 0.5;
+            }
+        }
+
+        Self { items }
+    }
+
+    /// Shows the timeline, returning the anchor of the item that was clicked.
+    pub fn ui(&self, ui: &mut egui::Ui) -> Option<String> {
+        let plot = Plot::new("timeline")
+            .legend(Legend::default())
+            .show_axes([true, false])
+            .show_grid([true, false])
+            .allow_zoom([true, false])
+            .allow_drag([true, false])
+            .allow_boxed_zoom(false)
+            .x_axis_formatter(|x, _, range| {
+                let month = ((x - x.floor()) * 12.0).round() as u8;
+                if range.end() - range.start() < 3.0 && month < 12 {
+                    YearMonth {
+                        year: x.floor() as u16,
+                        month: Some(month + 1),
+                    }
+                    .to_string()
+                } else {
+                    format!("{}", x.round())
+                }
+            })
+            .label_formatter(|_, _| String::new())
+            .height(ui.available_height());
+
+        let now = YearMonth::current();
+        plot.show(ui, |plot_ui| {
+            for lane in Lane::ALL {
+                let bars: Vec<Bar> = self
+                    .items
+                    .iter()
+                    .filter(|item| item.lane == lane)
+                    .map(|item| {
+                        Bar::new(item.y, item.end - item.start)
+                            .base_offset(item.start)
+                            .width(BAR_HEIGHT)
+                            .name(format!("{}\n{}", item.title, item.duration))
+                    })
+                    .collect();
+                if !bars.is_empty() {
+                    plot_ui.bar_chart(
+                        BarChart::new(bars)
+                            .horizontal()
+                            .name(lane.name())
+                            .element_formatter(Box::new(|bar, _| bar.name.clone())),
+                    );
+                }
+            }
+            plot_ui.vline(VLine::new(now.as_year()).name(tr("timeline.today")));
+
+            // Label the bars whose title fits before the next item in the same row.
+            let bounds = plot_ui.plot_bounds();
+            let years_per_point =
+                bounds.width() / f64::from(plot_ui.response().rect.width().max(1.0));
+            for item in &self.items {
+                let title_width = item.title.chars().count() as f64 * 7.0 * years_per_point;
+                let next_start = self
+                    .items
+                    .iter()
+                    .filter(|other| other.y == item.y && other.start > item.start)
+                    .map(|other| other.start)
+                    .fold(f64::INFINITY, f64::min);
+                if item.start + title_width < next_start {
+                    plot_ui.text(
+                        Text::new(PlotPoint::new(item.start, item.y), item.title.as_str())
+                            .anchor(egui::Align2::LEFT_CENTER)
+                            .color(plot_ui.ctx().style().visuals.strong_text_color()),
+                    );
+                }
+            }
+
+            if !plot_ui.response().clicked() {
+                return None;
+            }
+            let pointer = plot_ui.pointer_coordinate()?;
+            self.items
+                .iter()
+                .find(|item| {
+                    (item.start..=item.end).contains(&pointer.x)
+                        && (pointer.y - item.y).abs() <= BAR_HEIGHT / 2.0
+                })
+                .map(|item| item.anchor.clone())
+        })
+        .inner
+    }
+}
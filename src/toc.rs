@@ -0,0 +1,72 @@
+/// A heading on the page the table of contents links to.
+#[derive(Clone, Debug)]
+pub struct TocEntry {
+    /// The section or project id.
+    pub anchor: String,
+    pub title: String,
+    /// 0 for sections, 1 for the projects within them.
+    pub depth: u8,
+    /// Top of the heading in screen coordinates, when it was last laid out.
+    top: f32,
+}
+
+/// Collects the headings of the central panel while it is laid out, so the
+/// table of contents always matches what is on the page.
+///
+/// The side panel is drawn before the central panel, so it shows the entries
+/// registered during the previous frame.
+#[derive(Default)]
+pub struct SectionRegistry {
+    entries: Vec<TocEntry>,
+    pending: Vec<TocEntry>,
+    /// Index into `entries` of the heading currently scrolled into view.
+    active: Option<usize>,
+}
+
+impl SectionRegistry {
+    /// Call before laying out the headings of a frame.
+    pub fn begin_frame(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn register(&mut self, anchor: &str, title: &str, depth: u8, heading: &egui::Response) {
+        self.pending.push(TocEntry {
+            anchor: anchor.to_owned(),
+            title: title.to_owned(),
+            depth,
+            top: heading.rect.top(),
+        });
+    }
+
+    /// Call after laying out the headings, with the top of the visible part of the page.
+    ///
+    /// The active entry is the last heading at or above the top of the view,
+    /// i.e. the one whose content is being read.
+    pub fn end_frame(&mut self, view_top: f32) {
+        std::mem::swap(&mut self.entries, &mut self.pending);
+        // Leave some slack so a heading scrolled to with `scroll_to_me` counts as active.
+        let threshold = view_top + 40.0;
+        self.active = self
+            .entries
+            .iter()
+            .rposition(|entry| entry.top <= threshold)
+            .or((!self.entries.is_empty()).then_some(0));
+    }
+
+    pub fn entries(&self) -> &[TocEntry] {
+        &self.entries
+    }
+
+    pub fn active(&self) -> Option<&TocEntry> {
+        self.active.map(|index| &self.entries[index])
+    }
+
+    /// The top-level entry containing the active entry.
+    pub fn active_section(&self) -> Option<&TocEntry> {
+        let active = self.active?;
+        self.entries[..=active]
+            .iter()
+            .rev()
+            .find(|entry| entry.depth == 0)
+    }
+}
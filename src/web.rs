@@ -0,0 +1,117 @@
+//! Browser integration for the web build.
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The URL fragment without the leading `#`, if there is one.
+pub fn fragment() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let fragment = hash.strip_prefix('#').unwrap_or(&hash);
+    (!fragment.is_empty()).then(|| fragment.to_owned())
+}
+
+/// The absolute URL of a file deployed next to the app, e.g. `projects/chess.png`.
+pub fn asset_url(path: &str) -> Option<String> {
+    let base = web_sys::window()?.document()?.base_uri().ok()??;
+    let url = web_sys::Url::new_with_base(path, &base).ok()?;
+    Some(url.href())
+}
+
+/// The user's preferred languages as BCP 47 tags, most preferred first.
+pub fn browser_languages() -> Vec<String> {
+    let Some(navigator) = web_sys::window().map(|window| window.navigator()) else {
+        return Vec::new();
+    };
+    let mut languages: Vec<String> = navigator
+        .languages()
+        .iter()
+        .filter_map(|language| language.as_string())
+        .collect();
+    if languages.is_empty() {
+        languages.extend(navigator.language());
+    }
+    languages
+}
+
+/// Opens the browser's print dialog for an HTML document instead of the canvas.
+///
+/// The document is loaded into a hidden frame, which prints without opening a window.
+pub fn print_html(html: &str) {
+    const FRAME_ID: &str = "print_frame";
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let Some(body) = document.body() else {
+        return;
+    };
+    if let Some(previous) = document.get_element_by_id(FRAME_ID) {
+        previous.remove();
+    }
+    let frame: web_sys::HtmlIFrameElement = match document.create_element("iframe") {
+        Ok(frame) => frame.unchecked_into(),
+        Err(err) => {
+            log::warn!("Failed to create the print frame: {err:?}");
+            return;
+        }
+    };
+    frame.set_id(FRAME_ID);
+    frame
+        .set_attribute("style", "position: fixed; width: 0; height: 0; border: 0;")
+        .ok();
+    frame.set_srcdoc(html);
+
+    let loaded = frame.clone();
+    let print = Closure::once_into_js(move || {
+        if let Some(Err(err)) = loaded.content_window().map(|window| window.print()) {
+            log::warn!("Failed to print: {err:?}");
+        }
+    });
+    frame.set_onload(Some(print.unchecked_ref()));
+    if let Err(err) = body.append_child(&frame) {
+        log::warn!("Failed to add the print frame: {err:?}");
+    }
+}
+
+/// Points the URL at the anchor with a new history entry, so "back" returns to the previous anchor.
+pub fn push_fragment(anchor: &str) {
+    set_fragment(anchor, true);
+}
+
+/// Points the URL at the anchor without adding a history entry.
+pub fn replace_fragment(anchor: &str) {
+    set_fragment(anchor, false);
+}
+
+fn set_fragment(anchor: &str, push: bool) {
+    let Some(history) = web_sys::window().and_then(|window| window.history().ok()) else {
+        return;
+    };
+    let url = format!("#{anchor}");
+    let result = if push {
+        history.push_state_with_url(&JsValue::NULL, "", Some(&url))
+    } else {
+        history.replace_state_with_url(&JsValue::NULL, "", Some(&url))
+    };
+    if let Err(err) = result {
+        log::warn!("Failed to update the URL fragment: {err:?}");
+    }
+}
+
+/// Repaints when the user navigates with the back/forward buttons or edits the fragment,
+/// so the app picks up the new fragment without waiting for other input.
+pub fn repaint_on_navigation(ctx: &egui::Context) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let ctx = ctx.clone();
+    let callback = Closure::<dyn FnMut()>::new(move || ctx.request_repaint());
+    for event in ["popstate", "hashchange"] {
+        if let Err(err) =
+            window.add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())
+        {
+            log::warn!("Failed to listen to {event}: {err:?}");
+        }
+    }
+    // The listener lives as long as the page.
+    callback.forget();
+}